@@ -56,7 +56,7 @@ fn impl_approx(ast: &syn::DeriveInput) -> proc_macro::TokenStream {
                 .map(|(i, _)| syn::Index::from(i));
             let abs_diff: Vec<TokenStream> = names
                 .clone()
-                .map(|name| quote! { self.#name.abs_diff_eq(other.#name, epsilon) })
+                .map(|name| quote! { AbsDiffEq::abs_diff_eq(&self.#name, &other.#name, epsilon) })
                 .collect();
             let rel_eq: Vec<TokenStream> = names
                 .clone()
@@ -0,0 +1,61 @@
+use std::{hint::black_box, time::Duration};
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use glam::DVec3 as Vec3;
+use simscript_physics::{
+    batch::Batch,
+    inertia_mass::{Inertia, InertiaMass, Mass},
+    momentum::Momentum,
+    panels::Panel,
+    transform::{Rotation, Transform, Translation},
+    State, StateBuilder,
+};
+
+fn rocket() -> State {
+    StateBuilder::new()
+        .mass(InertiaMass::new(
+            Mass::new(2.0),
+            Inertia::cylinder_x(1.2, 0.05, 2.0),
+        ))
+        .transform(Transform::new(
+            Translation::new(0., 0., 100.),
+            Rotation::from_y(0.3),
+        ))
+        .momentum(Momentum::from_vec3s(
+            Vec3::new(60., 0., 10.),
+            Vec3::new(0.01, 0.02, 0.),
+        ))
+        .add_panels(vec![
+            Panel::new(Vec3::new(-0.5, 0., 0.), Vec3::Y, 0.01),
+            Panel::new(Vec3::new(-0.5, 0., 0.), Vec3::Z, 0.01),
+            Panel::new(Vec3::new(0.6, 0., 0.), Vec3::X, 0.002),
+        ])
+        .build()
+}
+
+fn stepping(c: &mut Criterion) {
+    let delta = Duration::from_millis(1);
+
+    c.bench_function("velocity", |b| {
+        let state = rocket();
+        b.iter(|| black_box(&state).velocity())
+    });
+
+    c.bench_function("forward_euler", |b| {
+        let mut state = rocket();
+        b.iter(|| black_box(&mut state).forward_euler(delta))
+    });
+
+    c.bench_function("runge_kutta_4", |b| {
+        let mut state = rocket();
+        b.iter(|| black_box(&mut state).runge_kutta_4(delta))
+    });
+
+    c.bench_function("batch_runge_kutta_4_1000", |b| {
+        let mut batch = Batch::from_states(&vec![rocket(); 1000]).unwrap();
+        b.iter(|| black_box(&mut batch).runge_kutta_4(delta))
+    });
+}
+
+criterion_group!(benches, stepping);
+criterion_main!(benches);
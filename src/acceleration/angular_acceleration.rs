@@ -0,0 +1,38 @@
+#[cfg(feature = "approx")]
+use approx::{AbsDiffEq, RelativeEq, UlpsEq};
+
+use super::{Acceleration, LinAcc};
+use crate::quantity::vector_quantity;
+use glam::DVec3 as Vec3;
+
+vector_quantity! {
+    /// Angular acceleration in 3D space.
+    ///
+    /// This struct wraps a [Vec3] to provide a strongly typed representation of angular
+    /// acceleration, making operations and transformations explicit.
+    pub struct AngAcc;
+    "angular acceleration"
+}
+
+impl AngAcc {
+    /// Converts [AngAcc] into an [Acceleration] with zero linear acceleration.
+    #[inline]
+    #[must_use]
+    pub const fn to_acc(self) -> Acceleration {
+        Acceleration::new(LinAcc::ZERO, self)
+    }
+
+    /// Creates an [Acceleration] from [AngAcc] with a specified linear acceleration.
+    #[inline]
+    #[must_use]
+    pub const fn with_linear(self, lin: LinAcc) -> Acceleration {
+        Acceleration::new(lin, self)
+    }
+}
+
+impl From<Acceleration> for AngAcc {
+    #[inline]
+    fn from(value: Acceleration) -> Self {
+        value.angular
+    }
+}
@@ -0,0 +1,38 @@
+#[cfg(feature = "approx")]
+use approx::{AbsDiffEq, RelativeEq, UlpsEq};
+
+use super::{Acceleration, AngAcc};
+use crate::quantity::vector_quantity;
+use glam::DVec3 as Vec3;
+
+vector_quantity! {
+    /// Linear acceleration in 3D space.
+    ///
+    /// This struct wraps a [Vec3] to provide a strongly typed representation of linear
+    /// acceleration, making operations and transformations explicit.
+    pub struct LinAcc;
+    "linear acceleration"
+}
+
+impl LinAcc {
+    /// Converts [LinAcc] into an [Acceleration] with zero angular acceleration.
+    #[inline]
+    #[must_use]
+    pub const fn to_acc(self) -> Acceleration {
+        Acceleration::new(self, AngAcc::ZERO)
+    }
+
+    /// Creates an [Acceleration] from [LinAcc] with a specified angular acceleration.
+    #[inline]
+    #[must_use]
+    pub const fn with_angular(self, ang: AngAcc) -> Acceleration {
+        Acceleration::new(self, ang)
+    }
+}
+
+impl From<Acceleration> for LinAcc {
+    #[inline]
+    fn from(value: Acceleration) -> Self {
+        value.linear
+    }
+}
@@ -0,0 +1,243 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "approx")]
+use {
+    approx::{AbsDiffEq, RelativeEq, UlpsEq},
+    approx_derive::Approx,
+};
+
+use crate::overload::overload;
+pub use angular_acceleration::AngAcc;
+use core::{iter::Sum, time::Duration};
+use glam::DVec3 as Vec3;
+pub use linear_acceleration::LinAcc;
+
+use crate::{
+    inertia_mass::InertiaMass,
+    moments::{Moment, Torque},
+    velocity::{AngVel, Velocity},
+};
+
+mod angular_acceleration;
+mod linear_acceleration;
+
+/// Represents an acceleration with both linear and angular components.
+///
+/// This struct encapsulates translational acceleration [Acceleration::linear] and rotational
+/// acceleration [Acceleration::angular], completing the chain from [Moment] to [Velocity].
+#[cfg_attr(feature = "approx", derive(Approx))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Acceleration {
+    /// Linear acceleration component.
+    pub linear: LinAcc,
+    /// Angular acceleration component.
+    pub angular: AngAcc,
+}
+
+impl Acceleration {
+    /// Zero acceleration constant.
+    pub const ZERO: Self = Self::new(LinAcc::ZERO, AngAcc::ZERO);
+
+    /// Constructs a new `Acceleration` from given linear and angular accelerations.
+    #[inline]
+    #[must_use]
+    pub const fn new(lin: LinAcc, ang: AngAcc) -> Self {
+        Self {
+            linear: lin,
+            angular: ang,
+        }
+    }
+
+    /// Creates an acceleration with only a linear component.
+    #[inline]
+    #[must_use]
+    pub const fn from_linear(v: LinAcc) -> Self {
+        Self::new(v, AngAcc::ZERO)
+    }
+
+    /// Creates an acceleration with only an angular component.
+    #[inline]
+    #[must_use]
+    pub const fn from_angular(v: AngAcc) -> Self {
+        Self::new(LinAcc::ZERO, v)
+    }
+
+    /// Constructs an `Acceleration` from raw vector representations of linear and angular
+    /// acceleration.
+    #[inline]
+    #[must_use]
+    pub const fn from_vec3s(lin: Vec3, ang: Vec3) -> Self {
+        Self::new(LinAcc::from_vec3(lin), AngAcc::from_vec3(ang))
+    }
+
+    /// Constructs an `Acceleration` from a raw vector representing only linear acceleration.
+    #[inline]
+    #[must_use]
+    pub const fn from_linear_vec3(v: Vec3) -> Self {
+        Self::from_vec3s(v, Vec3::ZERO)
+    }
+
+    /// Constructs an `Acceleration` from a raw vector representing only angular acceleration.
+    #[inline]
+    #[must_use]
+    pub const fn from_angular_vec3(v: Vec3) -> Self {
+        Self::from_vec3s(Vec3::ZERO, v)
+    }
+}
+
+impl Acceleration {
+    /// Scales the acceleration by a time duration in seconds, returning a [Velocity].
+    #[inline]
+    #[must_use]
+    pub fn mul_secs(&self, rhs: f64) -> Velocity {
+        Velocity::new(self.linear.mul_secs(rhs), self.angular.mul_secs(rhs))
+    }
+
+    /// Scales the acceleration by a [Duration], returning a [Velocity].
+    ///
+    /// Internally, this calls [Acceleration::mul_secs] using [Duration::as_secs_f64].
+    #[inline]
+    #[must_use]
+    pub fn mul_dur(&self, rhs: &Duration) -> Velocity {
+        self.mul_secs(rhs.as_secs_f64())
+    }
+}
+
+/// Acceleration from applied loads
+impl InertiaMass {
+    /// Computes the angular acceleration caused by a torque on a spinning body.
+    ///
+    /// Solves Euler's rotation equation `α = I⁻¹·(τ - ω × I·ω)`, including the gyroscopic term.
+    /// The torque, angular velocity and inertia must all be expressed in the same frame, use
+    /// [InertiaMass::rotated] to bring the inertia into the world frame first.
+    ///
+    /// # Arguments
+    /// * `torque` - The applied [Torque]
+    /// * `ang_vel` - The current [AngVel] of the body
+    ///
+    /// # Returns
+    /// The resulting [AngAcc]
+    #[inline]
+    #[must_use]
+    pub fn angular_acceleration(&self, torque: &Torque, ang_vel: &AngVel) -> AngAcc {
+        let gyroscopic = ang_vel.0.cross(self.inertia.0.mul_vec3(ang_vel.0));
+        AngAcc(self.inv_inertia.0.mul_vec3(torque.0 - gyroscopic))
+    }
+
+    /// Computes the linear and angular acceleration caused by a [Moment].
+    ///
+    /// See [InertiaMass::angular_acceleration] for the angular part.
+    #[inline]
+    #[must_use]
+    pub fn acceleration(&self, moment: &Moment, ang_vel: &AngVel) -> Acceleration {
+        Acceleration::new(
+            moment.force / self.mass,
+            self.angular_acceleration(&moment.torque, ang_vel),
+        )
+    }
+}
+
+/// Conversion implementations to create `Acceleration` from individual components.
+impl From<LinAcc> for Acceleration {
+    #[inline]
+    fn from(value: LinAcc) -> Self {
+        Self::from_linear(value)
+    }
+}
+
+impl From<AngAcc> for Acceleration {
+    #[inline]
+    fn from(value: AngAcc) -> Self {
+        Self::from_angular(value)
+    }
+}
+
+/// Implements summation over an iterator of `Acceleration` values.
+impl Sum for Acceleration {
+    #[inline]
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |a, b| a + b)
+    }
+}
+
+overload!((a: ?Acceleration) + (b: ?Acceleration) -> Acceleration{ Acceleration::new(a.linear + b.linear, a.angular + b.angular) });
+overload!((a: ?Acceleration) - (b: ?Acceleration) -> Acceleration{ Acceleration::new(a.linear - b.linear, a.angular - b.angular) });
+overload!((a: &mut Acceleration) += (b: ?Acceleration) { a.linear += b.linear; a.angular += b.angular; });
+overload!((a: &mut Acceleration) -= (b: ?Acceleration) { a.linear -= b.linear; a.angular -= b.angular; });
+
+overload!((a: ?Acceleration) * (b: f64) -> Acceleration{ Acceleration::new(a.linear * b, a.angular * b) });
+overload!((a: ?Acceleration) / (b: f64) -> Acceleration{ Acceleration::new(a.linear / b, a.angular / b) });
+overload!((a: &mut Acceleration) *= (b: f64) { a.linear *= b; a.angular *= b; });
+overload!((a: &mut Acceleration) /= (b: f64) { a.linear /= b; a.angular /= b; });
+
+overload!((a: ?Acceleration) * (b: Duration) -> Velocity{ a.mul_dur(&b) });
+overload!((a: ?Acceleration) * (b: &Duration) -> Velocity{ a.mul_dur(b) });
+
+overload!(-(a: ?Acceleration) -> Acceleration{ Acceleration{ linear: -a.linear, angular: -a.angular }});
+
+#[cfg(test)]
+mod arithmetic {
+    use super::*;
+    use crate::{
+        inertia_mass::{Inertia, Mass},
+        moments::Force,
+        velocity::LinVel,
+    };
+    use approx::assert_ulps_eq;
+
+    #[test]
+    fn force_over_mass() {
+        let f = Force::new(3., -6., 9.);
+        let m = Mass::new(3.);
+
+        assert_ulps_eq!(f / m, LinAcc::new(1., -2., 3.));
+        assert_ulps_eq!((f / m) * m, f);
+    }
+
+    #[test]
+    fn times_duration() {
+        let a = Acceleration::new(LinAcc::new(2., 0., -4.), AngAcc::with_y(8.));
+        let dur = Duration::from_millis(500);
+
+        assert_ulps_eq!(a.linear * dur, LinVel::new(1., 0., -2.));
+        assert_ulps_eq!(a.angular * dur, AngVel::with_y(4.));
+        assert_ulps_eq!(
+            a * dur,
+            Velocity::new(LinVel::new(1., 0., -2.), AngVel::with_y(4.))
+        );
+    }
+
+    #[test]
+    fn sum() {
+        let total: Acceleration = [LinAcc::X.to_acc(), AngAcc::Z.to_acc(), LinAcc::Y.to_acc()]
+            .into_iter()
+            .sum();
+        assert_ulps_eq!(
+            total,
+            Acceleration::from_vec3s(Vec3::new(1., 1., 0.), Vec3::Z)
+        );
+    }
+
+    #[test]
+    fn principal_axis_spin_is_steady() {
+        let mass = InertiaMass::new(Mass::new(1.), Inertia::cylinder_x(2., 0.5, 1.));
+        let alpha = mass.angular_acceleration(&Torque::ZERO, &AngVel::with_x(10.));
+
+        assert_ulps_eq!(alpha, AngAcc::ZERO);
+    }
+
+    #[test]
+    fn gyroscopic() {
+        let inertia = Inertia::from_diagonal(Vec3::new(1., 2., 3.));
+        let mass = InertiaMass::new(Mass::new(1.), inertia);
+        let w = AngVel::new(1., 1., 0.);
+
+        // ω × Iω = (1, 1, 0) × (1, 2, 0) = (0, 0, 1)
+        let alpha = mass.angular_acceleration(&Torque::ZERO, &w);
+        assert_ulps_eq!(alpha, AngAcc::with_z(-1. / 3.));
+
+        let alpha = mass.acceleration(&Moment::from_torque(Torque::with_z(1.)), &w);
+        assert_ulps_eq!(alpha.angular, AngAcc::ZERO);
+    }
+}
@@ -0,0 +1,447 @@
+//! Stepping many states of the same model at once
+//!
+//! A [Batch] keeps one buffer of whole [Transform]s, one of [Momentum]s and so on, while the
+//! panels are stored only once. Each entity is still stepped on its own, with the same scalar
+//! equations as [State], but without cloning or allocating, and the result is bit-for-bit
+//! identical to stepping every state separately.
+//!
+//! States that don't share their panels can still be stepped together with [runge_kutta_4_all]
+//! and [forward_euler_all].
+
+use alloc::vec::Vec;
+use core::{error::Error, fmt, time::Duration};
+
+use crate::dynamics::Dynamics;
+use crate::inertia_mass::InertiaMass;
+use crate::momentum::Momentum;
+use crate::panels::Panel;
+use crate::renormalization::Renormalization;
+use crate::transform::Transform;
+use crate::State;
+
+/// Reasons a set of states can't be stored in a [Batch]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BatchError {
+    /// The state at the given index has different panels than the batch
+    PanelMismatch(usize),
+}
+
+impl fmt::Display for BatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::PanelMismatch(i) => write!(f, "state {i} has different panels than the batch"),
+        }
+    }
+}
+
+impl Error for BatchError {}
+
+/// States sharing the same panels, stored as one buffer per component
+///
+/// All buffers have the same length, the entity at index `i` is made up of the `i`th element of
+/// each of them.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Batch {
+    panels: Vec<Panel>,
+    masses: Vec<InertiaMass>,
+    transforms: Vec<Transform>,
+    momenta: Vec<Momentum>,
+    renormalizations: Vec<Renormalization>,
+}
+
+impl Batch {
+    /// Creates an empty batch whose entities will all use the given panels
+    pub fn new(panels: Vec<Panel>) -> Self {
+        Self::with_capacity(panels, 0)
+    }
+
+    /// Creates an empty batch with room for `capacity` entities
+    pub fn with_capacity(panels: Vec<Panel>, capacity: usize) -> Self {
+        Self {
+            panels,
+            masses: Vec::with_capacity(capacity),
+            transforms: Vec::with_capacity(capacity),
+            momenta: Vec::with_capacity(capacity),
+            renormalizations: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Collects states into a batch, using the panels of the first one
+    ///
+    /// # Errors
+    /// Returns [BatchError::PanelMismatch] if any state has different panels than the first
+    pub fn from_states(states: &[State]) -> Result<Self, BatchError> {
+        let panels = states.first().map(|s| s.panels.clone()).unwrap_or_default();
+        let mut batch = Self::with_capacity(panels, states.len());
+
+        for (i, state) in states.iter().enumerate() {
+            if state.panels != batch.panels {
+                return Err(BatchError::PanelMismatch(i));
+            }
+            batch.push_parts(
+                state.mass,
+                state.transform,
+                state.momentum,
+                state.renormalization,
+            );
+        }
+
+        Ok(batch)
+    }
+
+    /// Adds an entity using the panels of the batch and the default renormalization
+    pub fn push(&mut self, mass: InertiaMass, transform: Transform, momentum: Momentum) {
+        self.push_parts(mass, transform, momentum, Renormalization::DEFAULT);
+    }
+
+    /// Adds a state to the batch
+    ///
+    /// # Errors
+    /// Returns [BatchError::PanelMismatch] with the index the state would have had if its panels
+    /// differ from the ones of the batch
+    pub fn push_state(&mut self, state: &State) -> Result<(), BatchError> {
+        if state.panels != self.panels {
+            return Err(BatchError::PanelMismatch(self.len()));
+        }
+        self.push_parts(
+            state.mass,
+            state.transform,
+            state.momentum,
+            state.renormalization,
+        );
+        Ok(())
+    }
+
+    fn push_parts(
+        &mut self,
+        mass: InertiaMass,
+        transform: Transform,
+        momentum: Momentum,
+        renormalization: Renormalization,
+    ) {
+        self.masses.push(mass);
+        self.transforms.push(transform);
+        self.momenta.push(momentum);
+        self.renormalizations.push(renormalization);
+    }
+
+    /// Returns the number of entities in the batch
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.masses.len()
+    }
+
+    /// Returns `true` if the batch holds no entities
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.masses.is_empty()
+    }
+
+    /// Returns the panels shared by every entity
+    #[must_use]
+    pub fn panels(&self) -> &[Panel] {
+        &self.panels
+    }
+
+    /// Returns the mass of each entity, in the order they were added
+    #[must_use]
+    pub fn masses(&self) -> &[InertiaMass] {
+        &self.masses
+    }
+
+    /// Returns the transform of each entity, in the order they were added
+    #[must_use]
+    pub fn transforms(&self) -> &[Transform] {
+        &self.transforms
+    }
+
+    /// Returns the transform of each entity for modification, such as teleporting them
+    #[must_use]
+    pub fn transforms_mut(&mut self) -> &mut [Transform] {
+        &mut self.transforms
+    }
+
+    /// Returns the momentum of each entity, in the order they were added
+    #[must_use]
+    pub fn momenta(&self) -> &[Momentum] {
+        &self.momenta
+    }
+
+    /// Returns the momentum of each entity for modification, such as applying impulses
+    #[must_use]
+    pub fn momenta_mut(&mut self) -> &mut [Momentum] {
+        &mut self.momenta
+    }
+
+    /// Returns the renormalization settings of each entity, in the order they were added
+    #[must_use]
+    pub fn renormalizations(&self) -> &[Renormalization] {
+        &self.renormalizations
+    }
+
+    /// Reassembles the entity at `index` into a [State], cloning the panels
+    #[must_use]
+    pub fn state(&self, index: usize) -> Option<State> {
+        Some(State {
+            mass: *self.masses.get(index)?,
+            transform: self.transforms[index],
+            momentum: self.momenta[index],
+            panels: self.panels.clone(),
+            renormalization: self.renormalizations[index],
+        })
+    }
+
+    /// Reassembles every entity into a [State]
+    #[must_use]
+    pub fn to_states(&self) -> Vec<State> {
+        (0..self.len()).filter_map(|i| self.state(i)).collect()
+    }
+}
+
+/// Time step functions
+///
+/// Equivalent to calling the function of the same name on every [State] in the batch. With the
+/// `rayon` feature the entities are stepped on multiple threads.
+impl Batch {
+    /// Steps every entity forward by a [Duration] using the Forward Euler method
+    pub fn forward_euler(&mut self, time: Duration) {
+        self.for_each(|dynamics, transform, momentum, renormalization| {
+            dynamics.forward_euler(transform, momentum, renormalization, time);
+        });
+    }
+
+    /// Steps every entity forward by a [Duration] using the Runge Kutta 4 method
+    pub fn runge_kutta_4(&mut self, delta: Duration) {
+        self.for_each(|dynamics, transform, momentum, renormalization| {
+            dynamics.runge_kutta_4(transform, momentum, renormalization, delta);
+        });
+    }
+
+    /// Walks the buffers in lockstep, calling `f` once for every entity
+    fn for_each<F>(&mut self, f: F)
+    where
+        F: Fn(Dynamics<'_>, &mut Transform, &mut Momentum, &mut Renormalization) + Send + Sync,
+    {
+        let panels = &self.panels;
+
+        #[cfg(feature = "rayon")]
+        {
+            use rayon::prelude::*;
+
+            (
+                &self.masses,
+                &mut self.transforms,
+                &mut self.momenta,
+                &mut self.renormalizations,
+            )
+                .into_par_iter()
+                .for_each(|(mass, transform, momentum, renormalization)| {
+                    f(
+                        Dynamics::new(mass, panels),
+                        transform,
+                        momentum,
+                        renormalization,
+                    );
+                });
+        }
+
+        #[cfg(not(feature = "rayon"))]
+        for (((mass, transform), momentum), renormalization) in self
+            .masses
+            .iter()
+            .zip(&mut self.transforms)
+            .zip(&mut self.momenta)
+            .zip(&mut self.renormalizations)
+        {
+            f(
+                Dynamics::new(mass, panels),
+                transform,
+                momentum,
+                renormalization,
+            );
+        }
+    }
+}
+
+/// Steps every state forward by a [Duration] using the Forward Euler method
+///
+/// With the `rayon` feature the states are stepped on multiple threads.
+pub fn forward_euler_all(states: &mut [State], time: Duration) {
+    for_each_state(states, |state| state.forward_euler(time));
+}
+
+/// Steps every state forward by a [Duration] using the Runge Kutta 4 method
+///
+/// With the `rayon` feature the states are stepped on multiple threads.
+pub fn runge_kutta_4_all(states: &mut [State], delta: Duration) {
+    for_each_state(states, |state| state.runge_kutta_4(delta));
+}
+
+fn for_each_state(states: &mut [State], f: impl Fn(&mut State) + Send + Sync) {
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+        states.par_iter_mut().for_each(f);
+    }
+
+    #[cfg(not(feature = "rayon"))]
+    states.iter_mut().for_each(f);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use crate::inertia_mass::{Inertia, Mass};
+    use crate::transform::{Rotation, Translation};
+    use glam::DVec3 as Vec3;
+
+    fn panels() -> Vec<Panel> {
+        let mut panels = fixtures::fins();
+        panels.push(fixtures::nose());
+        panels
+    }
+
+    /// Rockets with slightly different masses, attitudes and launch velocities
+    fn states(n: usize) -> Vec<State> {
+        (0..n)
+            .map(|i| {
+                let i = i as f64;
+                fixtures::rocket()
+                    .mass(InertiaMass::new(
+                        Mass::new(2.0 + i * 0.01),
+                        Inertia::cylinder_x(1.2, 0.05, 2.0 + i * 0.01),
+                    ))
+                    .transform(Transform::new(
+                        Translation::new(0., 0., 100.),
+                        Rotation::from_y(0.3 + i * 0.02),
+                    ))
+                    .momentum(Momentum::from_vec3s(
+                        Vec3::new(60. + i, 0., 10.),
+                        Vec3::new(0.01, 0.02 * i, 0.),
+                    ))
+                    .renormalization(Renormalization::new(if i > 3. { 0. } else { 1e-12 }))
+                    .panels(panels())
+                    .build()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn runge_kutta_4_matches_states() {
+        let mut states = states(8);
+        let mut batch = Batch::from_states(&states).unwrap();
+
+        for _ in 0..500 {
+            batch.runge_kutta_4(Duration::from_millis(5));
+            states
+                .iter_mut()
+                .for_each(|s| s.runge_kutta_4(Duration::from_millis(5)));
+        }
+
+        assert_eq!(batch.to_states(), states);
+    }
+
+    #[test]
+    fn forward_euler_matches_states() {
+        let mut states = states(8);
+        let mut batch = Batch::from_states(&states).unwrap();
+
+        for _ in 0..500 {
+            batch.forward_euler(Duration::from_millis(5));
+            states
+                .iter_mut()
+                .for_each(|s| s.forward_euler(Duration::from_millis(5)));
+        }
+
+        assert_eq!(batch.to_states(), states);
+    }
+
+    #[test]
+    fn rejects_other_panels() {
+        let mut states = states(3);
+        states[2].panels.pop();
+
+        assert_eq!(
+            Batch::from_states(&states),
+            Err(BatchError::PanelMismatch(2))
+        );
+
+        let mut batch = Batch::new(panels());
+        assert_eq!(batch.push_state(&states[0]), Ok(()));
+        assert_eq!(
+            batch.push_state(&states[2]),
+            Err(BatchError::PanelMismatch(1))
+        );
+        assert_eq!(batch.len(), 1);
+    }
+
+    #[test]
+    fn state_round_trip() {
+        let states = states(4);
+        let batch = Batch::from_states(&states).unwrap();
+
+        assert_eq!(batch.state(2).as_ref(), states.get(2));
+        assert_eq!(batch.state(4), None);
+        assert!(Batch::from_states(&[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn steps_slices_of_states() {
+        let mut states = states(5);
+        let mut expected = states.clone();
+
+        runge_kutta_4_all(&mut states, Duration::from_millis(5));
+        forward_euler_all(&mut states, Duration::from_millis(5));
+        for state in &mut expected {
+            state.runge_kutta_4(Duration::from_millis(5));
+            state.forward_euler(Duration::from_millis(5));
+        }
+
+        assert_eq!(states, expected);
+    }
+
+    #[cfg(feature = "rayon")]
+    mod parallel {
+        use super::*;
+        use crate::dynamics::PANEL_CHUNK;
+
+        fn with_threads<T: Send>(n: usize, f: impl FnOnce() -> T + Send) -> T {
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(n)
+                .build()
+                .unwrap()
+                .install(f)
+        }
+
+        #[test]
+        fn independent_of_thread_count() {
+            let batch = Batch::from_states(&states(64)).unwrap();
+            let step = |mut batch: Batch| {
+                for _ in 0..50 {
+                    batch.runge_kutta_4(Duration::from_millis(5));
+                }
+                batch
+            };
+
+            let single = with_threads(1, || step(batch.clone()));
+            assert_eq!(with_threads(3, || step(batch.clone())), single);
+            assert_eq!(with_threads(8, || step(batch)), single);
+        }
+
+        #[test]
+        fn many_panels() {
+            let mut state = states(1).remove(0);
+            state.panels = (0..PANEL_CHUNK * 5 + 7)
+                .map(|i| {
+                    let i = i as f64;
+                    Panel::new(Vec3::new(i.sin(), i.cos(), 0.1), Vec3::new(1., i, -i), 1e-4)
+                })
+                .collect();
+
+            let single = with_threads(1, || state.panel_moment());
+            assert_eq!(with_threads(4, || state.panel_moment()), single);
+            assert_eq!(with_threads(7, || state.panel_moment()), single);
+        }
+    }
+}
@@ -1,9 +1,58 @@
 use crate::inertia_mass::InertiaMass;
 use crate::momentum::Momentum;
 use crate::panels::Panel;
+use crate::renormalization::Renormalization;
 use crate::transform::Transform;
 use crate::State;
 
+use alloc::vec::Vec;
+use core::{error::Error, fmt};
+
+/// Reasons a [StateBuilder] can fail to produce a valid [State]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BuildError {
+    /// No mass was set
+    MissingMass,
+    /// The mass is not a finite, positive number
+    InvalidMass,
+    /// The inertia tensor is not finite, not symmetric or not positive definite
+    InvalidInertia,
+    /// The transform contains NaN or infinite components
+    NonFiniteTransform,
+    /// The momentum contains NaN or infinite components
+    NonFiniteMomentum,
+    /// The rotation quaternion is not of unit length
+    NonUnitRotation,
+    /// The panel at the given index has a zero length or non-finite normal
+    ZeroPanelNormal(usize),
+    /// The panel at the given index has a non-finite offset
+    NonFinitePanelOffset(usize),
+    /// The panel at the given index has a negative or NaN area
+    NegativePanelArea(usize),
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingMass => write!(f, "mass must be set"),
+            Self::InvalidMass => write!(f, "mass must be finite and positive"),
+            Self::InvalidInertia => {
+                write!(f, "inertia must be finite, symmetric and positive definite")
+            }
+            Self::NonFiniteTransform => write!(f, "transform must be finite"),
+            Self::NonFiniteMomentum => write!(f, "momentum must be finite"),
+            Self::NonUnitRotation => write!(f, "rotation quaternion must be normalized"),
+            Self::ZeroPanelNormal(i) => {
+                write!(f, "panel {i} has a zero length or non-finite normal")
+            }
+            Self::NonFinitePanelOffset(i) => write!(f, "panel {i} has a non-finite offset"),
+            Self::NegativePanelArea(i) => write!(f, "panel {i} has a negative area"),
+        }
+    }
+}
+
+impl Error for BuildError {}
+
 /// Builder for `State`
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct StateBuilder {
@@ -11,6 +60,7 @@ pub struct StateBuilder {
     transform: Option<Transform>,
     momentum: Option<Momentum>,
     panels: Vec<Panel>,
+    renormalization: Renormalization,
 }
 
 impl StateBuilder {
@@ -21,6 +71,7 @@ impl StateBuilder {
             transform: None,
             momentum: None,
             panels: Vec::new(),
+            renormalization: Renormalization::DEFAULT,
         }
     }
 
@@ -60,6 +111,72 @@ impl StateBuilder {
         self
     }
 
+    /// Sets how the rotation is renormalized while stepping
+    pub const fn renormalization(mut self, renormalization: Renormalization) -> Self {
+        self.renormalization = renormalization;
+        self
+    }
+
+    /// Builds the `State`, validating every field
+    ///
+    /// # Errors
+    /// Returns a [BuildError] describing the first invalid field found
+    pub fn try_build(self) -> Result<State, BuildError> {
+        let mass = self.mass.ok_or(BuildError::MissingMass)?;
+        let transform = self.transform.unwrap_or(Transform::ZERO);
+        let momentum = self.momentum.unwrap_or(Momentum::ZERO);
+
+        if !mass.mass.0.is_finite() || mass.mass.0 <= 0. {
+            return Err(BuildError::InvalidMass);
+        }
+
+        // Checked before anything else touches the tensor, singular ones can't be decomposed
+        let inertia = mass.inertia.0;
+        let determinant = inertia.determinant();
+        if !inertia.is_finite() || !determinant.is_finite() || determinant == 0. {
+            return Err(BuildError::InvalidInertia);
+        }
+
+        let tolerance = mass.inertia.diagonal().abs().max_element() * 1e-9;
+        let symmetric = inertia.abs_diff_eq(inertia.transpose(), tolerance);
+        let (moments, _) = mass.inertia.principal_axes();
+        if !mass.inv_inertia.0.is_finite() || !symmetric || moments.min_element() <= 0. {
+            return Err(BuildError::InvalidInertia);
+        }
+
+        if !transform.translation.0.is_finite() || !transform.rotation.0.is_finite() {
+            return Err(BuildError::NonFiniteTransform);
+        }
+
+        if !transform.rotation.0.is_normalized() {
+            return Err(BuildError::NonUnitRotation);
+        }
+
+        if !momentum.linear.0.is_finite() || !momentum.angular.0.is_finite() {
+            return Err(BuildError::NonFiniteMomentum);
+        }
+
+        for (i, panel) in self.panels.iter().enumerate() {
+            if !panel.normal.is_finite() || panel.normal.length_squared() == 0. {
+                return Err(BuildError::ZeroPanelNormal(i));
+            }
+            if !panel.offset.is_finite() {
+                return Err(BuildError::NonFinitePanelOffset(i));
+            }
+            if panel.area < 0. || panel.area.is_nan() {
+                return Err(BuildError::NegativePanelArea(i));
+            }
+        }
+
+        Ok(State {
+            mass,
+            transform,
+            momentum,
+            panels: self.panels,
+            renormalization: self.renormalization,
+        })
+    }
+
     /// Builds the `State`, panicking if required fields are missing
     pub fn build(self) -> State {
         State {
@@ -67,6 +184,127 @@ impl StateBuilder {
             transform: self.transform.unwrap_or(Transform::ZERO),
             momentum: self.momentum.unwrap_or(Momentum::ZERO),
             panels: self.panels,
+            renormalization: self.renormalization,
         }
     }
 }
+
+#[cfg(test)]
+mod try_build {
+    use super::*;
+    use crate::inertia_mass::{Inertia, Mass};
+    use glam::{DMat3 as Mat3, DQuat as Quat, DVec3 as Vec3};
+
+    fn mass() -> InertiaMass {
+        InertiaMass::new(Mass::new(1.), Inertia::cylinder_x(1., 0.1, 1.))
+    }
+
+    #[test]
+    fn valid() {
+        let state = StateBuilder::new()
+            .mass(mass())
+            .add_panel(Panel::new(Vec3::X, Vec3::Y, 1.))
+            .try_build();
+
+        assert_eq!(
+            state,
+            Ok(StateBuilder::new()
+                .mass(mass())
+                .add_panel(Panel::new(Vec3::X, Vec3::Y, 1.))
+                .build())
+        );
+    }
+
+    #[test]
+    fn missing_mass() {
+        assert_eq!(
+            StateBuilder::new().try_build(),
+            Err(BuildError::MissingMass)
+        );
+    }
+
+    #[test]
+    fn invalid_mass() {
+        let mass = InertiaMass::new(Mass::new(-1.), Inertia::cylinder_x(1., 0.1, 1.));
+        let state = StateBuilder::new().mass(mass).try_build();
+        assert_eq!(state, Err(BuildError::InvalidMass));
+    }
+
+    #[test]
+    fn invalid_inertia() {
+        let singular = InertiaMass::new(Mass::new(1.), Inertia::new(Mat3::ZERO));
+        let non_finite = InertiaMass::new(
+            Mass::new(1.),
+            Inertia::from_diagonal(Vec3::new(1., f64::NAN, 1.)),
+        );
+        let negative = InertiaMass::new(Mass::new(1.), Inertia::new(-Mat3::IDENTITY));
+        let skewed = InertiaMass::new(
+            Mass::new(1.),
+            Inertia::new(Mat3::from_cols_array_2d(&[
+                [1., 0.5, 0.],
+                [0., 1., 0.],
+                [0., 0., 1.],
+            ])),
+        );
+
+        for mass in [singular, non_finite, negative, skewed] {
+            let state = StateBuilder::new().mass(mass).try_build();
+            assert_eq!(state, Err(BuildError::InvalidInertia));
+        }
+    }
+
+    #[test]
+    fn non_finite() {
+        let transform = StateBuilder::new()
+            .mass(mass())
+            .transform(Transform::from_vec3(Vec3::new(f64::NAN, 0., 0.)))
+            .try_build();
+        let momentum = StateBuilder::new()
+            .mass(mass())
+            .momentum(Momentum::from_angular_vec3(Vec3::splat(f64::INFINITY)))
+            .try_build();
+
+        assert_eq!(transform, Err(BuildError::NonFiniteTransform));
+        assert_eq!(momentum, Err(BuildError::NonFiniteMomentum));
+    }
+
+    #[test]
+    fn non_unit_rotation() {
+        let state = StateBuilder::new()
+            .mass(mass())
+            .transform(Transform::from_quat(Quat::from_xyzw(0., 0., 0., 2.)))
+            .try_build();
+
+        assert_eq!(state, Err(BuildError::NonUnitRotation));
+    }
+
+    #[test]
+    fn invalid_panels() {
+        let valid = Panel::new(Vec3::X, Vec3::Y, 1.);
+        let zero_normal = StateBuilder::new()
+            .mass(mass())
+            .add_panels(vec![valid, Panel::new(Vec3::X, Vec3::ZERO, 1.)])
+            .try_build();
+        let negative_area = StateBuilder::new()
+            .mass(mass())
+            .add_panels(vec![valid, valid, Panel::new(Vec3::X, Vec3::Y, -1.)])
+            .try_build();
+
+        let nan_normal = StateBuilder::new()
+            .mass(mass())
+            .add_panel(Panel::new(Vec3::X, Vec3::new(f64::NAN, 1., 0.), 1.))
+            .try_build();
+        let infinite_offset = StateBuilder::new()
+            .mass(mass())
+            .add_panels(vec![
+                valid,
+                Panel::new(Vec3::splat(f64::INFINITY), Vec3::Y, 1.),
+            ])
+            .try_build();
+
+        assert_eq!(zero_normal, Err(BuildError::ZeroPanelNormal(1)));
+        assert_eq!(negative_area, Err(BuildError::NegativePanelArea(2)));
+        assert_eq!(nan_normal, Err(BuildError::ZeroPanelNormal(0)));
+        assert_eq!(infinite_offset, Err(BuildError::NonFinitePanelOffset(1)));
+    }
+}
@@ -0,0 +1,389 @@
+//! Saving a [Simulation] to resume it later
+//!
+//! A checkpoint holds everything a [Simulation] needs to continue: the states including their
+//! renormalization counters, the integrator, step size, end condition, elapsed time and step
+//! count. Resuming from a checkpoint continues bit-for-bit as if the run was never interrupted.
+//!
+//! Checkpoints are JSON documents with a `version` field. Older versions are upgraded on load
+//! by the functions in [MIGRATIONS].
+
+use std::{
+    error::Error,
+    fmt,
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::scenario::{ScenarioError, Simulation};
+use crate::{BuildError, StateBuilder};
+
+/// Version of the checkpoints written by [Simulation::save_checkpoint]
+pub const CHECKPOINT_VERSION: u32 = 1;
+
+/// Upgrades a checkpoint from one version to the next
+pub type Migration = fn(&mut Value) -> Result<(), CheckpointError>;
+
+/// Migrations from every older version, the first one upgrades version 1 to version 2
+///
+/// When the format changes, bump [CHECKPOINT_VERSION] and append the function upgrading the
+/// previous version, so that `MIGRATIONS.len() + 1 == CHECKPOINT_VERSION` always holds.
+pub const MIGRATIONS: &[Migration] = &[];
+
+/// A saved [Simulation]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub version: u32,
+    pub simulation: Simulation,
+}
+
+impl Checkpoint {
+    /// Captures a simulation at the current version
+    pub fn new(simulation: Simulation) -> Self {
+        Self {
+            version: CHECKPOINT_VERSION,
+            simulation,
+        }
+    }
+
+    /// Parses a checkpoint of any supported version, upgrading it to the current one
+    ///
+    /// The simulation is validated like one built from a scenario, see [Simulation::validate]
+    /// and [StateBuilder::try_build].
+    ///
+    /// # Errors
+    /// Returns an error if the checkpoint is malformed, invalid or newer than this library
+    pub fn from_reader(reader: impl Read) -> Result<Self, CheckpointError> {
+        let value = serde_json::from_reader(reader)?;
+        let checkpoint: Self = serde_json::from_value(migrate(value, MIGRATIONS)?)?;
+        checkpoint.validate()?;
+        Ok(checkpoint)
+    }
+
+    /// Writes the checkpoint as JSON
+    ///
+    /// The simulation is validated first, like in [Checkpoint::from_reader], so that every
+    /// checkpoint written can be read back. JSON has no infinity or NaN, a simulation with
+    /// non-finite states is rejected instead of losing them.
+    ///
+    /// # Errors
+    /// Returns an error if the simulation is invalid, or any error from writing
+    pub fn to_writer(&self, writer: impl Write) -> Result<(), CheckpointError> {
+        self.validate()?;
+        Ok(serde_json::to_writer(writer, self)?)
+    }
+
+    /// Checks the simulation and every state in it
+    fn validate(&self) -> Result<(), CheckpointError> {
+        let sim = &self.simulation;
+        sim.validate().map_err(CheckpointError::Invalid)?;
+        if sim.names.len() != sim.states.len() {
+            return Err(CheckpointError::BodyCount {
+                names: sim.names.len(),
+                states: sim.states.len(),
+            });
+        }
+
+        for (body, state) in sim.states.iter().enumerate() {
+            StateBuilder::new()
+                .mass(state.mass)
+                .transform(state.transform)
+                .momentum(state.momentum)
+                .panels(state.panels.clone())
+                .renormalization(state.renormalization)
+                .try_build()
+                .map_err(|error| CheckpointError::InvalidState { body, error })?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Upgrades a checkpoint to the version after the last of `migrations`
+fn migrate(mut value: Value, migrations: &[Migration]) -> Result<Value, CheckpointError> {
+    let current = migrations.len() as u64 + 1;
+    let mut version = value
+        .get("version")
+        .and_then(Value::as_u64)
+        .ok_or(CheckpointError::MissingVersion)?;
+
+    if version == 0 || version > current {
+        return Err(CheckpointError::UnsupportedVersion(version));
+    }
+
+    while version < current {
+        migrations[version as usize - 1](&mut value)?;
+        version += 1;
+        value["version"] = version.into();
+    }
+
+    Ok(value)
+}
+
+impl Simulation {
+    /// Writes a checkpoint of the simulation
+    ///
+    /// # Errors
+    /// Returns an error if the simulation is invalid, see [Checkpoint::to_writer], or any error
+    /// from writing
+    pub fn save_checkpoint(&self, writer: impl Write) -> Result<(), CheckpointError> {
+        Checkpoint::new(self.clone()).to_writer(writer)
+    }
+
+    /// Resumes a simulation from a checkpoint of any supported version
+    ///
+    /// # Errors
+    /// Returns an error if the checkpoint is malformed, invalid or newer than this library
+    pub fn load_checkpoint(reader: impl Read) -> Result<Self, CheckpointError> {
+        Ok(Checkpoint::from_reader(reader)?.simulation)
+    }
+
+    /// Writes a checkpoint of the simulation to a file
+    ///
+    /// # Errors
+    /// Returns an error if the simulation is invalid, or any error from creating or writing the
+    /// file
+    pub fn save_checkpoint_file(&self, path: impl AsRef<Path>) -> Result<(), CheckpointError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.save_checkpoint(&mut writer)?;
+        Ok(writer.flush()?)
+    }
+
+    /// Resumes a simulation from a checkpoint file
+    ///
+    /// # Errors
+    /// Returns an error if the file can't be read, see [Simulation::load_checkpoint]
+    pub fn load_checkpoint_file(path: impl AsRef<Path>) -> Result<Self, CheckpointError> {
+        Self::load_checkpoint(BufReader::new(File::open(path)?))
+    }
+}
+
+/// Reasons a checkpoint can't be saved or loaded
+#[derive(Debug)]
+pub enum CheckpointError {
+    Io(io::Error),
+    /// The checkpoint isn't valid JSON or doesn't match its version's format
+    Json(serde_json::Error),
+    /// The checkpoint has no `version` field
+    MissingVersion,
+    /// The checkpoint was written by a newer or unknown version of the format
+    UnsupportedVersion(u64),
+    /// A migration couldn't upgrade the checkpoint
+    Migration(String),
+    /// The step size or end condition is invalid
+    Invalid(ScenarioError),
+    /// The number of body names doesn't match the number of states
+    BodyCount {
+        names: usize,
+        states: usize,
+    },
+    /// The state of the body at the given index is invalid or not finite
+    InvalidState {
+        body: usize,
+        error: BuildError,
+    },
+}
+
+impl fmt::Display for CheckpointError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "checkpoint io error: {e}"),
+            Self::Json(e) => write!(f, "invalid checkpoint: {e}"),
+            Self::MissingVersion => write!(f, "checkpoint has no version"),
+            Self::UnsupportedVersion(v) => write!(
+                f,
+                "checkpoint version {v} is not supported, the latest is {CHECKPOINT_VERSION}"
+            ),
+            Self::Migration(message) => write!(f, "couldn't upgrade checkpoint: {message}"),
+            Self::Invalid(e) => write!(f, "invalid checkpoint: {e}"),
+            Self::BodyCount { names, states } => {
+                write!(f, "checkpoint has {names} body names for {states} states")
+            }
+            Self::InvalidState { body, error } => {
+                write!(f, "invalid checkpoint: body {body}: {error}")
+            }
+        }
+    }
+}
+
+impl Error for CheckpointError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Json(e) => Some(e),
+            Self::Invalid(e) => Some(e),
+            Self::InvalidState { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for CheckpointError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for CheckpointError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Json(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use crate::renormalization::Renormalization;
+    use crate::scenario::{self, EndCondition};
+    use serde_json::json;
+    use std::time::Duration;
+
+    const SCENARIO: &str = concat!(
+        r#"
+step = 0.003
+end = { time = 0.1 }
+
+"#,
+        fixtures::rocket_toml!(),
+        r#"rotation = [0.0, 0.3, 0.0]
+velocity = [30.0, 0.0, 5.0]
+angular_velocity = [0.2, -0.1, 0.4]
+renormalization_tolerance = 0.0
+"#
+    );
+
+    #[test]
+    fn resumes_bit_for_bit() {
+        let mut sim = scenario::load_str(SCENARIO).unwrap();
+        for _ in 0..10 {
+            sim.step();
+        }
+
+        let mut bytes = Vec::new();
+        sim.save_checkpoint(&mut bytes).unwrap();
+        let mut resumed = Simulation::load_checkpoint(bytes.as_slice()).unwrap();
+        assert_eq!(resumed, sim);
+
+        sim.run();
+        resumed.run();
+        assert_eq!(resumed, sim);
+        assert!(sim.states[0].renormalization.count > 0);
+    }
+
+    #[test]
+    fn keeps_disabled_renormalization() {
+        let mut sim = scenario::load_str(SCENARIO).unwrap();
+        sim.states[0].renormalization = Renormalization::DISABLED;
+        sim.step();
+
+        let mut bytes = Vec::new();
+        sim.save_checkpoint(&mut bytes).unwrap();
+        let resumed = Simulation::load_checkpoint(bytes.as_slice()).unwrap();
+        assert_eq!(resumed, sim);
+        assert_eq!(resumed.states[0].renormalization.tolerance, f64::INFINITY);
+    }
+
+    #[test]
+    fn rejects_invalid_states() {
+        let mut sim = scenario::load_str(SCENARIO).unwrap();
+        sim.states[0].momentum.linear.0.x = f64::NAN;
+        let mut bytes = Vec::new();
+        assert!(matches!(
+            sim.save_checkpoint(&mut bytes),
+            Err(CheckpointError::InvalidState {
+                body: 0,
+                error: BuildError::NonFiniteMomentum
+            })
+        ));
+        assert!(bytes.is_empty());
+
+        let sim = scenario::load_str(SCENARIO).unwrap();
+        let mut value = serde_json::to_value(Checkpoint::new(sim)).unwrap();
+        value["simulation"]["states"][0]["panels"][1]["area"] = json!(-1.);
+        assert!(matches!(
+            Checkpoint::from_reader(value.to_string().as_bytes()),
+            Err(CheckpointError::InvalidState {
+                body: 0,
+                error: BuildError::NegativePanelArea(1)
+            })
+        ));
+    }
+
+    #[test]
+    fn rejects_unknown_versions() {
+        let sim = scenario::load_str(SCENARIO).unwrap();
+        let mut value = serde_json::to_value(Checkpoint::new(sim)).unwrap();
+
+        value["version"] = json!(CHECKPOINT_VERSION + 1);
+        assert!(matches!(
+            Checkpoint::from_reader(value.to_string().as_bytes()),
+            Err(CheckpointError::UnsupportedVersion(v)) if v == CHECKPOINT_VERSION as u64 + 1
+        ));
+
+        value.as_object_mut().unwrap().remove("version");
+        assert!(matches!(
+            Checkpoint::from_reader(value.to_string().as_bytes()),
+            Err(CheckpointError::MissingVersion)
+        ));
+    }
+
+    #[test]
+    fn rejects_invalid_simulations() {
+        let reload = |sim: &Simulation| {
+            let mut bytes = Vec::new();
+            assert!(sim.save_checkpoint(&mut bytes).is_err());
+            bytes = serde_json::to_vec(&Checkpoint::new(sim.clone())).unwrap();
+            Simulation::load_checkpoint(bytes.as_slice())
+        };
+
+        let mut sim = scenario::load_str(SCENARIO).unwrap();
+        sim.step = Duration::ZERO;
+        assert!(matches!(
+            reload(&sim),
+            Err(CheckpointError::Invalid(ScenarioError::InvalidStep { .. }))
+        ));
+
+        let mut sim = scenario::load_str(SCENARIO).unwrap();
+        sim.end = EndCondition::Time(-1.);
+        assert!(matches!(
+            reload(&sim),
+            Err(CheckpointError::Invalid(ScenarioError::InvalidEnd { .. }))
+        ));
+
+        let mut sim = scenario::load_str(SCENARIO).unwrap();
+        sim.names.push("ghost".to_owned());
+        assert!(matches!(
+            reload(&sim),
+            Err(CheckpointError::BodyCount {
+                names: 2,
+                states: 1
+            })
+        ));
+    }
+
+    #[test]
+    fn migrates_in_order() {
+        fn rename_sim(value: &mut Value) -> Result<(), CheckpointError> {
+            let sim = value["sim"].take();
+            value["simulation"] = sim;
+            Ok(())
+        }
+        fn fail(_: &mut Value) -> Result<(), CheckpointError> {
+            Err(CheckpointError::Migration("no".into()))
+        }
+
+        let migrated = migrate(json!({ "version": 1, "sim": 5 }), &[rename_sim]).unwrap();
+        assert_eq!(
+            migrated,
+            json!({ "version": 2, "sim": null, "simulation": 5 })
+        );
+
+        let current = migrate(json!({ "version": 2 }), &[fail]).unwrap();
+        assert_eq!(current, json!({ "version": 2 }));
+        assert!(migrate(json!({ "version": 1 }), &[fail]).is_err());
+    }
+}
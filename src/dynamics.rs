@@ -0,0 +1,188 @@
+//! The equations of motion shared by [State](crate::State) and [Batch](crate::batch::Batch)
+//!
+//! Both step through the exact same functions so that stepping a batch is bit-for-bit equivalent
+//! to stepping every state on its own.
+
+#[cfg(feature = "rayon")]
+use alloc::vec::Vec;
+use core::time::Duration;
+
+use glam::DMat3 as Mat3;
+
+use crate::hooks::Stage;
+use crate::inertia_mass::InertiaMass;
+use crate::moments::Moment;
+use crate::momentum::Momentum;
+use crate::panels::Panel;
+use crate::renormalization::Renormalization;
+use crate::transform::Transform;
+use crate::velocity::{AngVel, Velocity};
+
+/// Number of panels summed sequentially before their moments are added together
+pub(crate) const PANEL_CHUNK: usize = 1024;
+
+/// The parts of a simulated entity that don't change while stepping
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Dynamics<'a> {
+    pub mass: &'a InertiaMass,
+    pub panels: &'a [Panel],
+}
+
+impl<'a> Dynamics<'a> {
+    pub fn new(mass: &'a InertiaMass, panels: &'a [Panel]) -> Self {
+        Self { mass, panels }
+    }
+
+    /// Returns the rotation of the transform as a [Mat3]
+    ///
+    /// Computed once per evaluation and shared between everything that needs it
+    pub fn rotation_matrix(transform: &Transform) -> Mat3 {
+        Mat3::from_quat(transform.rotation.0)
+    }
+
+    /// Computes the velocity using an already computed rotation matrix
+    pub fn velocity_at(&self, momentum: &Momentum, rot: &Mat3) -> Velocity {
+        Velocity::new(
+            momentum.linear / self.mass.mass,
+            AngVel(self.mass.rotated_inv_inertia(rot).0 * momentum.angular.0),
+        )
+    }
+
+    /// Computes the panel moment using an already computed velocity
+    ///
+    /// The panels are summed in chunks of [PANEL_CHUNK], which are then summed in order. With the
+    /// `rayon` feature the chunks are summed in parallel, the grouping of the additions and thus
+    /// the result stay the same regardless of the number of threads.
+    pub fn panel_moment_at(&self, transform: &Transform, vel: &Velocity) -> Moment {
+        let rot = transform.rotation.0;
+        let chunk_moment = |chunk: &[Panel]| {
+            chunk
+                .iter()
+                .map(|panel| panel.to_moment(vel, &rot))
+                .fold(Moment::ZERO, |acc, e| acc + e)
+        };
+
+        #[cfg(feature = "rayon")]
+        if self.panels.len() > PANEL_CHUNK {
+            use rayon::prelude::*;
+
+            let moments: Vec<Moment> = self
+                .panels
+                .par_chunks(PANEL_CHUNK)
+                .map(chunk_moment)
+                .collect();
+            return moments.into_iter().fold(Moment::ZERO, |acc, e| acc + e);
+        }
+
+        self.panels
+            .chunks(PANEL_CHUNK)
+            .map(chunk_moment)
+            .fold(Moment::ZERO, |acc, e| acc + e)
+    }
+
+    /// Evaluates the velocity and the panel moment, sharing a single rotation matrix
+    pub fn derivative(&self, transform: &Transform, momentum: &Momentum) -> (Velocity, Moment) {
+        let vel = self.velocity_at(momentum, &Self::rotation_matrix(transform));
+        (vel, self.panel_moment_at(transform, &vel))
+    }
+
+    /// Steps forward by a [Duration] using the Forward Euler method
+    pub fn forward_euler(
+        &self,
+        transform: &mut Transform,
+        momentum: &mut Momentum,
+        renormalization: &mut Renormalization,
+        time: Duration,
+    ) {
+        self.forward_euler_observed(transform, momentum, renormalization, time, |_| {});
+    }
+
+    /// [Dynamics::forward_euler], passing its single evaluation of the panels to `observe`
+    pub fn forward_euler_observed(
+        &self,
+        transform: &mut Transform,
+        momentum: &mut Momentum,
+        renormalization: &mut Renormalization,
+        time: Duration,
+        mut observe: impl FnMut(&Stage),
+    ) {
+        let rot = Self::rotation_matrix(transform);
+
+        let velocity = self.velocity_at(momentum, &rot);
+        let moment = self.panel_moment_at(transform, &velocity);
+        observe(&Stage {
+            index: 0,
+            offset: Duration::ZERO,
+            transform: *transform,
+            momentum: *momentum,
+            velocity,
+            moment,
+        });
+        *momentum += moment * time;
+
+        let velocity = self.velocity_at(momentum, &rot);
+        *transform += velocity * time;
+
+        renormalization.apply(&mut transform.rotation);
+    }
+
+    /// Steps forward by a [Duration] using the Runge Kutta 4 method
+    pub fn runge_kutta_4(
+        &self,
+        transform: &mut Transform,
+        momentum: &mut Momentum,
+        renormalization: &mut Renormalization,
+        delta: Duration,
+    ) {
+        self.runge_kutta_4_observed(transform, momentum, renormalization, delta, |_| {});
+    }
+
+    /// [Dynamics::runge_kutta_4], passing each of its four stages to `observe`
+    pub fn runge_kutta_4_observed(
+        &self,
+        transform: &mut Transform,
+        momentum: &mut Momentum,
+        renormalization: &mut Renormalization,
+        delta: Duration,
+        mut observe: impl FnMut(&Stage),
+    ) {
+        let offsets = [Duration::ZERO, delta / 2, delta / 2, delta];
+        let mut stage = |index: usize, transform: Transform, momentum: Momentum| {
+            let (velocity, moment) = self.derivative(&transform, &momentum);
+            observe(&Stage {
+                index,
+                offset: offsets[index],
+                transform,
+                momentum,
+                velocity,
+                moment,
+            });
+            (velocity, moment)
+        };
+
+        let delta = delta.as_secs_f64();
+        let half_delta = delta / 2.;
+
+        let (k1_x, k1_p) = stage(0, *transform, *momentum);
+        let (k2_x, k2_p) = stage(
+            1,
+            *transform + k1_x.mul_secs(half_delta),
+            *momentum + k1_p.mul_secs(half_delta),
+        );
+        let (k3_x, k3_p) = stage(
+            2,
+            *transform + k2_x.mul_secs(half_delta),
+            *momentum + k2_p.mul_secs(half_delta),
+        );
+        let (k4_x, k4_p) = stage(
+            3,
+            *transform + k3_x.mul_secs(delta),
+            *momentum + k3_p.mul_secs(delta),
+        );
+
+        *momentum += (k1_p + k2_p * 2. + k3_p * 2. + k4_p).mul_secs(delta / 6.);
+        *transform += (k1_x + k2_x * 2. + k3_x * 2. + k4_x).mul_secs(delta / 6.);
+
+        renormalization.apply(&mut transform.rotation);
+    }
+}
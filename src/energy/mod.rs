@@ -0,0 +1,68 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "approx")]
+use {
+    approx::{AbsDiffEq, RelativeEq, UlpsEq},
+    approx_derive::Approx,
+};
+
+use crate::quantity::scalar_ops;
+
+pub use power::Power;
+
+mod power;
+
+/// An amount of energy in joules.
+///
+/// Used for kinetic and potential energy as well as for work, see [Work].
+#[cfg_attr(feature = "approx", derive(Approx))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Energy(pub f64);
+
+/// Work done on an entity, energy transferred to it by a force over a distance.
+pub type Work = Energy;
+
+impl Energy {
+    /// Zero energy constant.
+    pub const ZERO: Self = Self::new(0.);
+
+    /// Creates a new [Energy] from a value in joules.
+    #[inline]
+    #[must_use]
+    pub const fn new(joules: f64) -> Self {
+        Self(joules)
+    }
+}
+
+scalar_ops!(Energy, f64);
+
+#[cfg(test)]
+mod arithmetic {
+    use super::*;
+    use approx::assert_ulps_eq;
+    use std::time::Duration;
+
+    #[test]
+    fn same_type() {
+        let a = Energy::new(12.5);
+        let b = Energy::new(2.5);
+
+        assert_ulps_eq!((a + b).0, 15.);
+        assert_ulps_eq!((a - b).0, 10.);
+        assert_ulps_eq!((-a).0, -12.5);
+        assert_ulps_eq!([a, b, b].into_iter().sum::<Energy>().0, 17.5);
+        assert!(a > b);
+    }
+
+    #[test]
+    fn time() {
+        let e = Energy::new(30.);
+        let p = Power::new(4.);
+        let dur = Duration::from_millis(1500);
+
+        assert_ulps_eq!((e / dur).0, 20.);
+        assert_ulps_eq!((p * dur).0, 6.);
+        assert_ulps_eq!(((e / dur) * dur).0, e.0);
+    }
+}
@@ -0,0 +1,63 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "approx")]
+use {
+    approx::{AbsDiffEq, RelativeEq, UlpsEq},
+    approx_derive::Approx,
+};
+
+use crate::quantity::scalar_ops;
+use crate::{
+    moments::{Force, Moment, Torque},
+    velocity::{AngVel, LinVel, Velocity},
+};
+
+/// Rate of energy transfer in watts.
+#[cfg_attr(feature = "approx", derive(Approx))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Power(pub f64);
+
+impl Power {
+    /// Zero power constant.
+    pub const ZERO: Self = Self::new(0.);
+
+    /// Creates a new [Power] from a value in watts.
+    #[inline]
+    #[must_use]
+    pub const fn new(watts: f64) -> Self {
+        Self(watts)
+    }
+}
+
+scalar_ops!(Power, f64);
+
+/// Power delivered by loads
+impl Force {
+    /// Returns the power delivered by the force to a point moving at `vel`.
+    #[inline]
+    #[must_use]
+    pub fn power(&self, vel: &LinVel) -> Power {
+        Power(self.0.dot(vel.0))
+    }
+}
+
+impl Torque {
+    /// Returns the power delivered by the torque to a body rotating at `vel`.
+    #[inline]
+    #[must_use]
+    pub fn power(&self, vel: &AngVel) -> Power {
+        Power(self.0.dot(vel.0))
+    }
+}
+
+impl Moment {
+    /// Returns the power delivered by the moment to a body moving at `vel`.
+    ///
+    /// The moment and velocity must be taken about the same point.
+    #[inline]
+    #[must_use]
+    pub fn power(&self, vel: &Velocity) -> Power {
+        self.force.power(&vel.linear) + self.torque.power(&vel.angular)
+    }
+}
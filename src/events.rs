@@ -0,0 +1,555 @@
+//! Stopping or reacting exactly when something happens during a simulation
+//!
+//! An [Event] is a scalar function of the time and [State] that changes sign when the event
+//! happens, such as the altitude on ground impact. [Events::step] evaluates every event at the end
+//! of a step and, when one changes sign, locates the crossing with Brent's method by re-stepping
+//! from the start with shorter steps. The state is left just past the earliest crossing, where the
+//! event's callback may modify it.
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::{slice, time::Duration};
+
+use crate::hooks::{self, Observer, Stage};
+use crate::integrator::Integrator;
+use crate::State;
+
+/// Which sign changes of an [Event] function count as the event happening
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// From negative to zero or positive
+    Rising,
+    /// From positive to zero or negative
+    Falling,
+    /// Either way
+    #[default]
+    Either,
+}
+
+/// What to do after an event happened
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventAction {
+    /// Stop the simulation at the event
+    #[default]
+    Stop,
+    /// Keep going from the event
+    Continue,
+}
+
+type EventFn = Box<dyn Fn(Duration, &State) -> f64>;
+type Callback = Box<dyn FnMut(Duration, &mut State) -> EventAction>;
+
+/// A condition to locate during integration
+pub struct Event {
+    function: EventFn,
+    direction: Direction,
+    tolerance: Duration,
+    callback: Option<Callback>,
+}
+
+impl Event {
+    /// The default precision of the located event time
+    pub const DEFAULT_TOLERANCE: Duration = Duration::from_micros(1);
+
+    /// Creates an event happening whenever `function` of the elapsed time and state changes sign
+    ///
+    /// Without a callback the event stops the simulation. A NaN value never counts as crossed.
+    pub fn new(function: impl Fn(Duration, &State) -> f64 + 'static) -> Self {
+        Self {
+            function: Box::new(function),
+            direction: Direction::Either,
+            tolerance: Self::DEFAULT_TOLERANCE,
+            callback: None,
+        }
+    }
+
+    /// The vertical velocity crossing zero on the way down
+    pub fn apogee() -> Self {
+        Self::new(|_, state| state.velocity().linear.0.z).falling()
+    }
+
+    /// The altitude crossing zero on the way down
+    pub fn ground_impact() -> Self {
+        Self::new(|_, state| state.transform.translation.0.z).falling()
+    }
+
+    /// A fixed point in time, such as the burnout of a motor with a known burn time
+    pub fn at(time: Duration) -> Self {
+        Self::new(move |now, _| now.as_secs_f64() - time.as_secs_f64()).rising()
+    }
+
+    /// The predicate becoming `true`
+    ///
+    /// The crossing is found by bisecting on the predicate, which must not flicker within a step.
+    pub fn when(predicate: impl Fn(&State) -> bool + 'static) -> Self {
+        Self::new(move |_, state| if predicate(state) { 1. } else { -1. }).rising()
+    }
+
+    /// Only counts the function going from negative to zero or positive
+    #[must_use]
+    pub fn rising(self) -> Self {
+        self.direction(Direction::Rising)
+    }
+
+    /// Only counts the function going from positive to zero or negative
+    #[must_use]
+    pub fn falling(self) -> Self {
+        self.direction(Direction::Falling)
+    }
+
+    /// Sets which sign changes of the function count, both by default
+    #[must_use]
+    pub fn direction(mut self, direction: Direction) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Sets how precisely the time of the event is located
+    #[must_use]
+    pub fn tolerance(mut self, tolerance: Duration) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Calls `callback` with the time and state at the event, it may modify the state and decides
+    /// whether the simulation goes on
+    #[must_use]
+    pub fn then(
+        mut self,
+        callback: impl FnMut(Duration, &mut State) -> EventAction + 'static,
+    ) -> Self {
+        self.callback = Some(Box::new(callback));
+        self
+    }
+
+    /// Evaluates the event function
+    pub fn value(&self, time: Duration, state: &State) -> f64 {
+        (self.function)(time, state)
+    }
+
+    /// Returns whether going from `start` to `end` counts as the event happening, never if
+    /// either is NaN
+    fn crossed(&self, start: f64, end: f64) -> bool {
+        let rising = start < 0. && end >= 0.;
+        let falling = start > 0. && end <= 0.;
+
+        match self.direction {
+            Direction::Rising => rising,
+            Direction::Falling => falling,
+            Direction::Either => rising || falling,
+        }
+    }
+}
+
+impl core::fmt::Debug for Event {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Event")
+            .field("direction", &self.direction)
+            .field("tolerance", &self.tolerance)
+            .field("callback", &self.callback.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+/// An event that happened during [Events::step]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Occurrence {
+    /// Index of the body the event happened to, always 0 for [Events::step]
+    pub body: usize,
+    /// Index of the event, in the order they were added
+    pub index: usize,
+    /// Time of the event, just past the crossing
+    pub time: Duration,
+    /// What the callback of the event decided, [EventAction::Stop] without one
+    pub action: EventAction,
+}
+
+/// The result of [Events::step]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StepOutcome {
+    /// How far the state was stepped, shorter than requested if an event happened
+    pub elapsed: Duration,
+    /// The event that cut the step short, if any
+    pub event: Option<Occurrence>,
+}
+
+/// A set of events checked while stepping
+#[derive(Debug, Default)]
+pub struct Events {
+    events: Vec<Event>,
+}
+
+impl Events {
+    /// Creates an empty set of events
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an event, returning `self` for chaining
+    #[must_use]
+    pub fn with(mut self, event: Event) -> Self {
+        self.push(event);
+        self
+    }
+
+    /// Adds an event, its index is the number of events added before it
+    pub fn push(&mut self, event: Event) {
+        self.events.push(event);
+    }
+
+    /// Returns the number of events
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Returns whether there are no events
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Steps `state` forward from `time` by `delta`, stopping at the earliest event in between
+    ///
+    /// When an event happens the state is stepped to just past its crossing instead, within the
+    /// event's tolerance, and its callback is called. The rest of the step is not taken.
+    pub fn step(
+        &mut self,
+        state: &mut State,
+        integrator: Integrator,
+        time: Duration,
+        delta: Duration,
+    ) -> StepOutcome {
+        self.step_observed(state, integrator, time, delta, |_| {})
+    }
+
+    /// [Events::step], passing every evaluation of the panels to `observe`
+    ///
+    /// Only the stages of the step that is kept are observed, not those of the trial steps used to
+    /// locate an event.
+    pub fn step_observed(
+        &mut self,
+        state: &mut State,
+        integrator: Integrator,
+        time: Duration,
+        delta: Duration,
+        mut observe: impl FnMut(&Stage),
+    ) -> StepOutcome {
+        let (elapsed, event) = hooks::step_bodies(
+            slice::from_mut(state),
+            slice::from_mut(self),
+            integrator,
+            time,
+            delta,
+            &mut StageFn(&mut observe),
+        );
+
+        StepOutcome { elapsed, event }
+    }
+
+    /// Returns the index of the event happening first between `start` and `end`, a step of
+    /// `delta` later, along with the shortest step after which it has happened
+    ///
+    /// Events happening after `limit` are ignored.
+    pub(crate) fn earliest(
+        &self,
+        start: &State,
+        end: &State,
+        integrator: Integrator,
+        time: Duration,
+        delta: Duration,
+        limit: Duration,
+    ) -> Option<(usize, Duration)> {
+        let mut earliest: Option<(usize, Duration)> = None;
+
+        for (index, event) in self.events.iter().enumerate() {
+            let before = event.value(time, start);
+            if !event.crossed(before, event.value(time + delta, end)) {
+                continue;
+            }
+
+            let limit = earliest.map_or(limit, |(_, at)| at);
+            let Some(at) = locate(event, start, integrator, time, before, limit) else {
+                continue;
+            };
+            if earliest.is_none_or(|(_, earliest)| at < earliest) {
+                earliest = Some((index, at));
+            }
+        }
+
+        earliest
+    }
+
+    /// Calls the callback of the event at `index`, which happened at `time`
+    pub(crate) fn fire(&mut self, index: usize, time: Duration, state: &mut State) -> EventAction {
+        self.events[index]
+            .callback
+            .as_mut()
+            .map_or(EventAction::Stop, |callback| callback(time, state))
+    }
+}
+
+/// Passes the stages of [Events::step_observed] on to a closure
+struct StageFn<F>(F);
+
+impl<F: FnMut(&Stage)> Observer for StageFn<F> {
+    fn force_evaluation(&mut self, _: Duration, _: usize, stage: &Stage) {
+        (self.0)(stage);
+    }
+}
+
+/// Finds the shortest step from `start` after which `event` has happened, to within its tolerance
+///
+/// Returns `None` if the event hasn't happened by `limit`.
+fn locate(
+    event: &Event,
+    start: &State,
+    integrator: Integrator,
+    time: Duration,
+    before: f64,
+    limit: Duration,
+) -> Option<Duration> {
+    let g = |tau: f64| {
+        let mut state = start.clone();
+        let tau = clamp(tau, limit);
+        integrator.step(&mut state, tau);
+        event.value(time + tau, &state)
+    };
+
+    let after = |value: f64| event.crossed(before, value);
+    let b = limit.as_secs_f64();
+    let fb = g(b);
+    if !after(fb) {
+        return None;
+    }
+
+    let root = brent(
+        g,
+        after,
+        (0., before),
+        (b, fb),
+        event.tolerance.as_secs_f64(),
+    );
+    Some(clamp(root, limit))
+}
+
+/// Converts a step found by [brent] to a duration within `[0, limit]`, `limit` if it is NaN
+fn clamp(tau: f64, limit: Duration) -> Duration {
+    if tau.is_nan() {
+        limit
+    } else {
+        Duration::from_secs_f64(tau.clamp(0., limit.as_secs_f64()))
+    }
+}
+
+/// Brent's method, returning the end of the final bracket where `after` holds
+///
+/// `a` must be before the crossing and `b` after it.
+fn brent(
+    f: impl Fn(f64) -> f64,
+    after: impl Fn(f64) -> bool,
+    (mut a, mut fa): (f64, f64),
+    (mut b, mut fb): (f64, f64),
+    tolerance: f64,
+) -> f64 {
+    let (mut c, mut fc) = (a, fa);
+    let mut d = b - a;
+    let mut e = d;
+
+    for _ in 0..100 {
+        if after(fb) == after(fc) {
+            (c, fc) = (a, fa);
+            d = b - a;
+            e = d;
+        }
+        if fc.abs() < fb.abs() {
+            (a, fa) = (b, fb);
+            (b, fb) = (c, fc);
+            (c, fc) = (a, fa);
+        }
+
+        let tol = 2. * f64::EPSILON * b.abs() + 0.5 * tolerance;
+        let m = 0.5 * (c - b);
+        if m.abs() <= tol || fb == 0. {
+            break;
+        }
+
+        if e.abs() >= tol && fa.abs() > fb.abs() {
+            // Inverse quadratic interpolation, or the secant method with only two points
+            let s = fb / fa;
+            let (mut p, mut q) = if a == c {
+                (2. * m * s, 1. - s)
+            } else {
+                let q = fa / fc;
+                let r = fb / fc;
+                (
+                    s * (2. * m * q * (q - r) - (b - a) * (r - 1.)),
+                    (q - 1.) * (r - 1.) * (s - 1.),
+                )
+            };
+            if p > 0. {
+                q = -q;
+            } else {
+                p = -p;
+            }
+
+            if 2. * p < (3. * m * q - (tol * q).abs()).min((e * q).abs()) {
+                e = d;
+                d = p / q;
+            } else {
+                d = m;
+                e = d;
+            }
+        } else {
+            d = m;
+            e = d;
+        }
+
+        (a, fa) = (b, fb);
+        b += if d.abs() > tol { d } else { tol.copysign(m) };
+        fb = f(b);
+    }
+
+    if after(fb) {
+        b
+    } else {
+        c
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inertia_mass::{Inertia, InertiaMass, Mass};
+    use crate::momentum::Momentum;
+    use crate::panels::Panel;
+    use crate::transform::{Transform, Translation};
+    use crate::StateBuilder;
+    use approx::assert_relative_eq;
+    use glam::DVec3 as Vec3;
+
+    /// A body at 10 m falling at 3 m/s, slowed down by a drag panel if `area` isn't zero
+    fn falling(area: f64) -> State {
+        StateBuilder::new()
+            .mass(InertiaMass::new(
+                Mass::new(1.),
+                Inertia::cylinder_z(1., 0.1, 1.),
+            ))
+            .transform(Transform::from_translation(Translation::new(0., 0., 10.)))
+            .momentum(Momentum::from_linear_vec3(Vec3::new(1., 0., -3.)))
+            .add_panel(Panel::new(Vec3::ZERO, Vec3::Z, area))
+            .build()
+    }
+
+    /// Steps until an event stops the simulation, or for at most ten seconds
+    fn run(events: &mut Events, state: &mut State, delta: Duration) -> Option<Occurrence> {
+        let mut time = Duration::ZERO;
+        while time < Duration::from_secs(10) {
+            let outcome = events.step(state, Integrator::RungeKutta4, time, delta);
+            time += outcome.elapsed;
+            if let Some(event) = outcome.event.filter(|e| e.action == EventAction::Stop) {
+                return Some(event);
+            }
+        }
+        None
+    }
+
+    #[test]
+    fn ground_impact() {
+        let mut state = falling(0.);
+        let mut events =
+            Events::new().with(Event::ground_impact().tolerance(Duration::from_nanos(10)));
+
+        let event = run(&mut events, &mut state, Duration::from_millis(100)).unwrap();
+
+        assert_eq!(event.index, 0);
+        assert_relative_eq!(event.time.as_secs_f64(), 10. / 3., epsilon = 1e-8);
+        assert!(state.transform.translation.0.z <= 0.);
+        assert!(state.transform.translation.0.z > -1e-7);
+    }
+
+    #[test]
+    fn earliest_event_wins() {
+        let mut state = falling(0.1);
+        let mut events = Events::new()
+            .with(Event::ground_impact())
+            .with(Event::new(|_, state| state.transform.translation.0.x - 1.5))
+            .with(Event::at(Duration::from_millis(1250)));
+
+        let event = run(&mut events, &mut state, Duration::from_millis(500)).unwrap();
+
+        assert_eq!(event.index, 2);
+        assert_eq!(event.time, Duration::from_millis(1250));
+    }
+
+    #[test]
+    fn callbacks_modify_the_state() {
+        let mut state = falling(0.);
+        let bounce = Event::ground_impact().then(|_, state| {
+            state.momentum.linear.0.z *= -1.;
+            EventAction::Continue
+        });
+        let mut events = Events::new()
+            .with(bounce)
+            .with(Event::when(|state| state.transform.translation.0.z > 3.));
+
+        let event = run(&mut events, &mut state, Duration::from_millis(100)).unwrap();
+
+        assert_eq!(event.index, 1);
+        assert_relative_eq!(event.time.as_secs_f64(), 13. / 3., epsilon = 1e-5);
+        assert_relative_eq!(state.velocity().linear.0.z, 3.);
+    }
+
+    #[test]
+    fn direction() {
+        let mut state = falling(0.);
+        let mut events = Events::new().with(Event::ground_impact().rising());
+
+        assert_eq!(
+            run(&mut events, &mut state, Duration::from_millis(100)),
+            None
+        );
+    }
+
+    #[test]
+    fn brent_converges() {
+        let root = brent(|x| x * x * x - 2., |v| v >= 0., (0., -2.), (2., 6.), 1e-12);
+        assert_relative_eq!(root, 2f64.cbrt(), epsilon = 1e-12);
+        assert!(root.powi(3) >= 2.);
+    }
+
+    #[test]
+    fn nan_is_not_crossed() {
+        // Undefined between 0.3 and 0.6 s, past the crossing at 0.5 s
+        let value = |t: f64| {
+            if (0.3..0.6).contains(&t) {
+                f64::NAN
+            } else {
+                t - 0.5
+            }
+        };
+        let root = brent(value, |v| v >= 0., (0., -0.5), (1., 0.5), 1e-12);
+        assert_relative_eq!(root, 0.6, epsilon = 1e-12);
+
+        let mut state = falling(0.);
+        let mut events = Events::new().with(Event::new(move |t, _| value(t.as_secs_f64())));
+        let outcome = events.step(
+            &mut state,
+            Integrator::RungeKutta4,
+            Duration::ZERO,
+            Duration::from_secs(1),
+        );
+        assert_relative_eq!(outcome.elapsed.as_secs_f64(), 0.6, epsilon = 1e-6);
+
+        let mut events = Events::new().with(Event::new(|_, _| f64::NAN));
+        let outcome = events.step(
+            &mut state,
+            Integrator::RungeKutta4,
+            Duration::ZERO,
+            Duration::from_secs(1),
+        );
+        assert_eq!(outcome.elapsed, Duration::from_secs(1));
+        assert_eq!(outcome.event, None);
+    }
+}
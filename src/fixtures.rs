@@ -0,0 +1,50 @@
+//! Bodies shared by the test modules
+
+use crate::inertia_mass::{Inertia, InertiaMass, Mass};
+use crate::panels::Panel;
+use crate::StateBuilder;
+use alloc::vec;
+use alloc::vec::Vec;
+use glam::DVec3 as Vec3;
+
+/// 2 kg rocket along `x`, 1.2 m long with a 5 cm radius
+pub(crate) fn rocket_mass() -> InertiaMass {
+    InertiaMass::new(Mass::new(2.), Inertia::cylinder_x(1.2, 0.05, 2.))
+}
+
+/// The two tail fins of the rocket, 0.5 m behind its centre of mass
+pub(crate) fn fins() -> Vec<Panel> {
+    vec![
+        Panel::new(Vec3::new(-0.5, 0., 0.), Vec3::Y, 0.01),
+        Panel::new(Vec3::new(-0.5, 0., 0.), Vec3::Z, 0.01),
+    ]
+}
+
+/// Small panel on the nose of the rocket, facing forward
+pub(crate) fn nose() -> Panel {
+    Panel::new(Vec3::new(0.6, 0., 0.), Vec3::X, 0.002)
+}
+
+/// The rocket with its fins, at rest at the origin
+pub(crate) fn rocket() -> StateBuilder {
+    StateBuilder::new().mass(rocket_mass()).panels(fins())
+}
+
+/// The rocket as a scenario body, without its transform and velocities
+#[cfg(feature = "scenario")]
+macro_rules! rocket_toml {
+    () => {
+        r#"[[bodies]]
+name = "rocket"
+mass = 2.0
+inertia = { cylinder_x = { length = 1.2, radius = 0.05 } }
+panels = [
+    { offset = [-0.5, 0.0, 0.0], normal = [0.0, 1.0, 0.0], area = 0.01 },
+    { offset = [-0.5, 0.0, 0.0], normal = [0.0, 0.0, 1.0], area = 0.01 },
+]
+"#
+    };
+}
+
+#[cfg(feature = "scenario")]
+pub(crate) use rocket_toml;
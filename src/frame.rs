@@ -0,0 +1,273 @@
+//! Reference frames and frame-tagged quantities.
+//!
+//! Values expressed relative to the simulated entity live in the [Body] frame, values expressed
+//! relative to the simulation itself live in the [World] frame. Wrapping a quantity in [Framed]
+//! or a position in [Point] tags it with its frame at compile time, so that a body-frame force
+//! cannot be added to a world-frame force without an explicit conversion through
+//! [Transform::to_world] or [Transform::to_body].
+
+use core::{fmt::Debug, marker::PhantomData, ops};
+
+use glam::{DQuat as Quat, DVec3 as Vec3};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    moments::{Force, Moment, Torque},
+    momentum::{AngMom, LinMom, Momentum},
+    transform::Transform,
+    velocity::{AngVel, LinVel, Velocity},
+};
+
+/// A reference frame marker, implemented by [Body] and [World].
+pub trait Frame: Debug + Clone + Copy + PartialEq {}
+
+/// The frame attached to the simulated entity, rotating and moving with it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Body;
+
+/// The fixed frame of the simulation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct World;
+
+impl Frame for Body {}
+impl Frame for World {}
+
+/// Quantities that change between frames by rotation only.
+pub trait Rotate {
+    /// Returns the quantity rotated by the given quaternion.
+    #[must_use]
+    fn rotate(&self, rot: Quat) -> Self;
+}
+
+impl Rotate for Vec3 {
+    #[inline]
+    fn rotate(&self, rot: Quat) -> Self {
+        rot.mul_vec3(*self)
+    }
+}
+
+macro_rules! impl_rotate_vec3 {
+    ($($t:ty),*) => {
+        $(
+            impl Rotate for $t {
+                #[inline]
+                fn rotate(&self, rot: Quat) -> Self {
+                    Self(rot.mul_vec3(self.0))
+                }
+            }
+        )*
+    };
+}
+
+impl_rotate_vec3!(LinVel, AngVel, Force, Torque, LinMom, AngMom);
+
+impl Rotate for Velocity {
+    #[inline]
+    fn rotate(&self, rot: Quat) -> Self {
+        Self::new(self.linear.rotate(rot), self.angular.rotate(rot))
+    }
+}
+
+impl Rotate for Moment {
+    #[inline]
+    fn rotate(&self, rot: Quat) -> Self {
+        Self::new(self.force.rotate(rot), self.torque.rotate(rot))
+    }
+}
+
+impl Rotate for Momentum {
+    #[inline]
+    fn rotate(&self, rot: Quat) -> Self {
+        Self::new(self.linear.rotate(rot), self.angular.rotate(rot))
+    }
+}
+
+/// A quantity tagged with the [Frame] it is expressed in.
+///
+/// Only quantities in the same frame can be added or subtracted.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Framed<T, F: Frame>(pub T, PhantomData<F>);
+
+impl<T, F: Frame> Framed<T, F> {
+    /// Tags `value` as expressed in the frame `F`.
+    #[inline]
+    #[must_use]
+    pub const fn new(value: T) -> Self {
+        Self(value, PhantomData)
+    }
+
+    /// Discards the frame, returning the raw quantity.
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: ops::Add<Output = T>, F: Frame> ops::Add for Framed<T, F> {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.0 + rhs.0)
+    }
+}
+
+impl<T: ops::Sub<Output = T>, F: Frame> ops::Sub for Framed<T, F> {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.0 - rhs.0)
+    }
+}
+
+impl<T: ops::Neg<Output = T>, F: Frame> ops::Neg for Framed<T, F> {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        Self::new(-self.0)
+    }
+}
+
+/// A position tagged with the [Frame] it is expressed in.
+///
+/// Unlike a [Framed] vector, a point is affected by the translation of a [Transform] as well as
+/// its rotation.
+///
+/// With the `serde` feature it serializes as its position alone, the frame is not recorded.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(transparent))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point<F: Frame>(
+    pub Vec3,
+    #[cfg_attr(feature = "serde", serde(skip))] PhantomData<F>,
+);
+
+impl<F: Frame> Point<F> {
+    /// The origin of the frame `F`.
+    pub const ORIGIN: Self = Self::new(Vec3::ZERO);
+
+    /// Tags `pos` as a position in the frame `F`.
+    #[inline]
+    #[must_use]
+    pub const fn new(pos: Vec3) -> Self {
+        Self(pos, PhantomData)
+    }
+
+    /// Discards the frame, returning the raw position.
+    #[inline]
+    #[must_use]
+    pub const fn into_inner(self) -> Vec3 {
+        self.0
+    }
+}
+
+impl<F: Frame> ops::Add<Framed<Vec3, F>> for Point<F> {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Framed<Vec3, F>) -> Self {
+        Self::new(self.0 + rhs.0)
+    }
+}
+
+impl<F: Frame> ops::Sub for Point<F> {
+    type Output = Framed<Vec3, F>;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Framed<Vec3, F> {
+        Framed::new(self.0 - rhs.0)
+    }
+}
+
+/// Frame conversions
+impl Transform {
+    /// Expresses a body-frame quantity in the world frame.
+    ///
+    /// Only the rotation is applied, use [Transform::point_to_world] for positions.
+    #[inline]
+    #[must_use]
+    pub fn to_world<T: Rotate>(&self, value: Framed<T, Body>) -> Framed<T, World> {
+        Framed::new(value.0.rotate(self.rotation.0))
+    }
+
+    /// Expresses a world-frame quantity in the body frame.
+    ///
+    /// Only the rotation is applied, use [Transform::point_to_body] for positions.
+    #[inline]
+    #[must_use]
+    pub fn to_body<T: Rotate>(&self, value: Framed<T, World>) -> Framed<T, Body> {
+        Framed::new(value.0.rotate(self.rotation.0.inverse()))
+    }
+
+    /// Converts a position relative to the body into a position in the world.
+    #[inline]
+    #[must_use]
+    pub fn point_to_world(&self, point: Point<Body>) -> Point<World> {
+        Point::new(self.transform_point(point.0))
+    }
+
+    /// Converts a position in the world into a position relative to the body.
+    #[inline]
+    #[must_use]
+    pub fn point_to_body(&self, point: Point<World>) -> Point<Body> {
+        Point::new(self.inverse().transform_point(point.0))
+    }
+}
+
+#[cfg(test)]
+mod conversions {
+    use super::*;
+    use crate::transform::{Rotation, Translation};
+    use approx::assert_ulps_eq;
+    use std::f64::consts::FRAC_PI_2;
+
+    fn quarter_z() -> Transform {
+        Transform::new(Translation::new(1., 2., 3.), Rotation::from_z(FRAC_PI_2))
+    }
+
+    #[test]
+    fn vectors() {
+        let t = quarter_z();
+        let body = Framed::<_, Body>::new(Force::X);
+        let world = t.to_world(body);
+
+        assert_ulps_eq!(world.0, Force::Y);
+        assert_ulps_eq!(t.to_body(world).0, body.0);
+    }
+
+    #[test]
+    fn velocity_and_moment() {
+        let t = quarter_z();
+        let vel = Framed::<_, Body>::new(Velocity::new(LinVel::X, AngVel::Y));
+        let moment = Framed::<_, Body>::new(Moment::new(Force::NEG_Y, Torque::X));
+
+        assert_ulps_eq!(t.to_world(vel).0, Velocity::new(LinVel::Y, AngVel::NEG_X));
+        assert_ulps_eq!(t.to_world(moment).0, Moment::new(Force::X, Torque::Y));
+    }
+
+    #[test]
+    fn points() {
+        let t = quarter_z();
+        let body = Point::<Body>::new(Vec3::X);
+        let world = t.point_to_world(body);
+
+        assert_ulps_eq!(world.0, Vec3::new(1., 3., 3.));
+        assert_ulps_eq!(t.point_to_body(world).0, body.0);
+        assert_ulps_eq!(t.point_to_world(Point::ORIGIN).0, t.translation.0);
+    }
+
+    #[test]
+    fn same_frame_arithmetic() {
+        let a = Framed::<_, World>::new(Force::X);
+        let b = Framed::<_, World>::new(Force::Y);
+        let p = Point::<World>::new(Vec3::Z);
+
+        assert_ulps_eq!((a + b).0, Force::new(1., 1., 0.));
+        assert_ulps_eq!((a - b).0, Force::new(1., -1., 0.));
+        assert_ulps_eq!((p + Framed::new(Vec3::X)).0, Vec3::new(1., 0., 1.));
+        assert_ulps_eq!((p - Point::ORIGIN).0, Vec3::Z);
+    }
+}
@@ -0,0 +1,563 @@
+//! Attaching telemetry, visualization and controllers to a simulation loop
+//!
+//! An [Observer] is called before and after each step, on every evaluation of the panel forces
+//! and whenever an event happens. Observers attach to a scenario `Simulation` through
+//! `Simulation::step_observed`, with the `scenario` feature, or to a single [State] through a
+//! [Runner], which also works without the standard library.
+//!
+//! Both step through the same function: every body is stepped by the same amount, and when an
+//! event happens to any body all of them are only stepped up to it.
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::{slice, time::Duration};
+
+#[cfg(feature = "scenario")]
+use crate::events::StepOutcome;
+use crate::events::{EventAction, Events, Occurrence};
+use crate::integrator::Integrator;
+use crate::moments::Moment;
+use crate::momentum::Momentum;
+#[cfg(feature = "scenario")]
+use crate::scenario::Simulation;
+use crate::transform::Transform;
+use crate::velocity::Velocity;
+use crate::State;
+
+/// One evaluation of the equations of motion within a step
+///
+/// Forward Euler evaluates the panels once per step, Runge Kutta 4 four times at the intermediate
+/// states of its stages.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stage {
+    /// Position of the evaluation within the step, from 0 to 3 for Runge Kutta 4
+    pub index: usize,
+    /// Time of the evaluation since the start of the step
+    pub offset: Duration,
+    pub transform: Transform,
+    pub momentum: Momentum,
+    /// Velocity at the stage, expressed in the world frame
+    pub velocity: Velocity,
+    /// Sum of the panel moments at the stage
+    pub moment: Moment,
+}
+
+/// Callbacks on a simulation loop, every one does nothing by default
+///
+/// `time` is the time of the state when it is passed, or of the start of the step for
+/// [Observer::force_evaluation]. `body` is the index of the state in the simulation, always 0 for
+/// a [Runner].
+#[allow(unused_variables)]
+pub trait Observer {
+    /// Called for every body before every step, the state may be modified
+    fn pre_step(&mut self, time: Duration, body: usize, state: &mut State) {}
+
+    /// Called for every body after every step, including those cut short by an event
+    fn post_step(&mut self, time: Duration, body: usize, state: &mut State) {}
+
+    /// Called on every evaluation of the panel forces, in order
+    fn force_evaluation(&mut self, time: Duration, body: usize, stage: &Stage) {}
+
+    /// Called after an event's own callback, the state of the body it happened to may be modified
+    fn on_event(&mut self, event: &Occurrence, state: &mut State) {}
+}
+
+impl<O: Observer + ?Sized> Observer for &mut O {
+    fn pre_step(&mut self, time: Duration, body: usize, state: &mut State) {
+        (**self).pre_step(time, body, state);
+    }
+
+    fn post_step(&mut self, time: Duration, body: usize, state: &mut State) {
+        (**self).post_step(time, body, state);
+    }
+
+    fn force_evaluation(&mut self, time: Duration, body: usize, stage: &Stage) {
+        (**self).force_evaluation(time, body, stage);
+    }
+
+    fn on_event(&mut self, event: &Occurrence, state: &mut State) {
+        (**self).on_event(event, state);
+    }
+}
+
+impl<O: Observer + ?Sized> Observer for Box<O> {
+    fn pre_step(&mut self, time: Duration, body: usize, state: &mut State) {
+        (**self).pre_step(time, body, state);
+    }
+
+    fn post_step(&mut self, time: Duration, body: usize, state: &mut State) {
+        (**self).post_step(time, body, state);
+    }
+
+    fn force_evaluation(&mut self, time: Duration, body: usize, stage: &Stage) {
+        (**self).force_evaluation(time, body, stage);
+    }
+
+    fn on_event(&mut self, event: &Occurrence, state: &mut State) {
+        (**self).on_event(event, state);
+    }
+}
+
+/// Calls every observer in order
+impl<O: Observer> Observer for [O] {
+    fn pre_step(&mut self, time: Duration, body: usize, state: &mut State) {
+        for observer in self {
+            observer.pre_step(time, body, state);
+        }
+    }
+
+    fn post_step(&mut self, time: Duration, body: usize, state: &mut State) {
+        for observer in self {
+            observer.post_step(time, body, state);
+        }
+    }
+
+    fn force_evaluation(&mut self, time: Duration, body: usize, stage: &Stage) {
+        for observer in self {
+            observer.force_evaluation(time, body, stage);
+        }
+    }
+
+    fn on_event(&mut self, event: &Occurrence, state: &mut State) {
+        for observer in self {
+            observer.on_event(event, state);
+        }
+    }
+}
+
+/// Steps every state by `delta` from `time`, or only up to the earliest event of any of them
+///
+/// `events[i]` are the events of `states[i]`, states without an entry have none. Returns how far
+/// the states were stepped and the event that happened, if any.
+pub(crate) fn step_bodies(
+    states: &mut [State],
+    events: &mut [Events],
+    integrator: Integrator,
+    time: Duration,
+    delta: Duration,
+    observer: &mut (impl Observer + ?Sized),
+) -> (Duration, Option<Occurrence>) {
+    for (body, state) in states.iter_mut().enumerate() {
+        observer.pre_step(time, body, state);
+    }
+
+    if events.iter().all(Events::is_empty) {
+        for (body, state) in states.iter_mut().enumerate() {
+            integrator.step_observed(state, delta, |stage| {
+                observer.force_evaluation(time, body, stage);
+            });
+        }
+        for (body, state) in states.iter_mut().enumerate() {
+            observer.post_step(time + delta, body, state);
+        }
+        return (delta, None);
+    }
+
+    // Stages of the trial step are only passed on once it is known to be kept
+    let starts = states.to_vec();
+    let mut stages = Vec::new();
+    for (body, state) in states.iter_mut().enumerate() {
+        integrator.step_observed(state, delta, |stage| stages.push((body, *stage)));
+    }
+
+    let mut earliest: Option<(usize, usize, Duration)> = None;
+    for (body, events) in events.iter().enumerate().take(states.len()) {
+        let limit = earliest.map_or(delta, |(.., at)| at);
+        let found = events.earliest(&starts[body], &states[body], integrator, time, delta, limit);
+        if let Some((index, at)) = found {
+            if earliest.is_none_or(|(.., earliest)| at < earliest) {
+                earliest = Some((body, index, at));
+            }
+        }
+    }
+
+    let elapsed = earliest.map_or(delta, |(.., at)| at);
+    if elapsed == delta {
+        for (body, stage) in &stages {
+            observer.force_evaluation(time, *body, stage);
+        }
+    } else {
+        for ((body, state), start) in states.iter_mut().enumerate().zip(starts) {
+            *state = start;
+            integrator.step_observed(state, elapsed, |stage| {
+                observer.force_evaluation(time, body, stage);
+            });
+        }
+    }
+
+    let end = time + elapsed;
+    let occurrence = earliest.map(|(body, index, _)| {
+        let state = &mut states[body];
+        let occurrence = Occurrence {
+            body,
+            index,
+            time: end,
+            action: events[body].fire(index, end, state),
+        };
+        observer.on_event(&occurrence, state);
+        occurrence
+    });
+
+    for (body, state) in states.iter_mut().enumerate() {
+        observer.post_step(end, body, state);
+    }
+
+    (elapsed, occurrence)
+}
+
+/// Steps a single [State] with fixed steps, checking events and calling observers
+///
+/// The equivalent of `Simulation::step_observed` for a lone state, usable without the standard
+/// library.
+pub struct Runner<'a> {
+    pub state: State,
+    pub integrator: Integrator,
+    /// Step size, must not be zero
+    pub step: Duration,
+    /// Simulated time
+    pub time: Duration,
+    /// Number of steps taken
+    pub steps: u64,
+    pub events: Events,
+    observers: Vec<Box<dyn Observer + 'a>>,
+}
+
+impl<'a> Runner<'a> {
+    /// Creates a runner starting at time zero, without events or observers
+    ///
+    /// # Panics
+    /// Panics if `step` is zero, as the runner would never advance
+    pub fn new(state: State, integrator: Integrator, step: Duration) -> Self {
+        assert!(!step.is_zero(), "step must not be zero");
+
+        Self {
+            state,
+            integrator,
+            step,
+            time: Duration::ZERO,
+            steps: 0,
+            events: Events::new(),
+            observers: Vec::new(),
+        }
+    }
+
+    /// Sets the events checked on every step
+    #[must_use]
+    pub fn with_events(mut self, events: Events) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Attaches an observer, returning `self` for chaining
+    ///
+    /// Pass `&mut observer` to keep access to it once the runner is done.
+    #[must_use]
+    pub fn observe(mut self, observer: impl Observer + 'a) -> Self {
+        self.push_observer(observer);
+        self
+    }
+
+    /// Attaches an observer, observers are called in the order they were attached
+    pub fn push_observer(&mut self, observer: impl Observer + 'a) {
+        self.observers.push(Box::new(observer));
+    }
+
+    /// Takes a single step, returning the event it stopped at if any
+    pub fn step(&mut self) -> Option<Occurrence> {
+        self.step_by(self.step)
+    }
+
+    /// Steps until `end`, shortening the last step to land on it
+    ///
+    /// Returns early with the event that stopped the simulation, if any
+    ///
+    /// # Panics
+    /// Panics if [Runner::step] was set to zero
+    pub fn run_until(&mut self, end: Duration) -> Option<Occurrence> {
+        assert!(!self.step.is_zero(), "step must not be zero");
+
+        while self.time < end {
+            let event = self.step_by(self.step.min(end - self.time));
+            if let Some(event) = event.filter(|e| e.action == EventAction::Stop) {
+                return Some(event);
+            }
+        }
+
+        None
+    }
+
+    fn step_by(&mut self, delta: Duration) -> Option<Occurrence> {
+        let (elapsed, event) = step_bodies(
+            slice::from_mut(&mut self.state),
+            slice::from_mut(&mut self.events),
+            self.integrator,
+            self.time,
+            delta,
+            self.observers.as_mut_slice(),
+        );
+
+        self.time += elapsed;
+        self.steps += 1;
+        event
+    }
+}
+
+impl core::fmt::Debug for Runner<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Runner")
+            .field("state", &self.state)
+            .field("integrator", &self.integrator)
+            .field("step", &self.step)
+            .field("time", &self.time)
+            .field("steps", &self.steps)
+            .field("events", &self.events)
+            .field("observers", &self.observers.len())
+            .finish()
+    }
+}
+
+#[cfg(feature = "scenario")]
+impl Simulation {
+    /// [Simulation::step], checking events and calling an observer
+    ///
+    /// `events[i]` are the events of body `i`, bodies without an entry have none. When an event
+    /// happens every body is only stepped up to it. The result is bit-for-bit the same as
+    /// [Simulation::step] as long as no event happens and the observer leaves the states alone.
+    ///
+    /// Returns `None` without stepping if the simulation is finished.
+    pub fn step_observed(
+        &mut self,
+        events: &mut [Events],
+        observer: &mut (impl Observer + ?Sized),
+    ) -> Option<StepOutcome> {
+        if self.is_finished() {
+            return None;
+        }
+
+        let delta = self.step.min(self.remaining());
+        let (elapsed, event) = step_bodies(
+            &mut self.states,
+            events,
+            self.integrator,
+            self.time,
+            delta,
+            observer,
+        );
+
+        self.time += elapsed;
+        self.steps += 1;
+        Some(StepOutcome { elapsed, event })
+    }
+
+    /// Steps until the [EndCondition](crate::scenario::EndCondition) is reached or an event
+    /// stops the simulation, returning that event
+    pub fn run_observed(
+        &mut self,
+        events: &mut [Events],
+        observer: &mut (impl Observer + ?Sized),
+    ) -> Option<Occurrence> {
+        while let Some(outcome) = self.step_observed(events, observer) {
+            if let Some(event) = outcome.event.filter(|e| e.action == EventAction::Stop) {
+                return Some(event);
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::Event;
+    use crate::fixtures;
+    use crate::transform::{Rotation, Translation};
+    use glam::DVec3 as Vec3;
+
+    /// A spinning body at 10 m falling at 3 m/s, slowed down by drag panels
+    fn state() -> State {
+        fixtures::rocket()
+            .transform(Transform::new(
+                Translation::new(0., 0., 10.),
+                Rotation::from_y(0.3),
+            ))
+            .momentum(Momentum::from_vec3s(
+                Vec3::new(20., 0., -6.),
+                Vec3::new(0.01, 0.02, 0.),
+            ))
+            .build()
+    }
+
+    #[derive(Debug, Default)]
+    struct Log {
+        calls: Vec<(&'static str, Duration)>,
+        stages: Vec<Stage>,
+    }
+
+    impl Observer for Log {
+        fn pre_step(&mut self, time: Duration, _: usize, _: &mut State) {
+            self.calls.push(("pre", time));
+        }
+
+        fn post_step(&mut self, time: Duration, _: usize, _: &mut State) {
+            self.calls.push(("post", time));
+        }
+
+        fn force_evaluation(&mut self, time: Duration, _: usize, stage: &Stage) {
+            self.calls.push(("force", time + stage.offset));
+            self.stages.push(*stage);
+        }
+
+        fn on_event(&mut self, event: &Occurrence, _: &mut State) {
+            self.calls.push(("event", event.time));
+        }
+    }
+
+    #[test]
+    fn stages() {
+        let delta = Duration::from_millis(10);
+        for (integrator, offsets) in [
+            (Integrator::ForwardEuler, &[0][..]),
+            (Integrator::RungeKutta4, &[0, 5, 5, 10]),
+        ] {
+            let mut stages = Vec::new();
+            let mut observed = state();
+            integrator.step_observed(&mut observed, delta, |stage| stages.push(*stage));
+
+            let mut expected = state();
+            integrator.step(&mut expected, delta);
+            assert_eq!(observed, expected);
+
+            let offsets: Vec<Duration> = offsets
+                .iter()
+                .map(|&ms| Duration::from_millis(ms))
+                .collect();
+            assert_eq!(stages.iter().map(|s| s.offset).collect::<Vec<_>>(), offsets);
+            assert_eq!(stages[0].transform, state().transform);
+            assert_eq!(stages[0].moment, state().panel_moment());
+            assert!(stages.iter().enumerate().all(|(i, s)| s.index == i));
+        }
+    }
+
+    #[test]
+    fn call_order() {
+        let mut log = Log::default();
+        let mut runner = Runner::new(state(), Integrator::ForwardEuler, Duration::from_millis(30))
+            .observe(&mut log);
+        assert_eq!(runner.run_until(Duration::from_millis(50)), None);
+        assert_eq!(runner.steps, 2);
+        drop(runner);
+
+        let ms = Duration::from_millis;
+        assert_eq!(
+            log.calls,
+            [
+                ("pre", ms(0)),
+                ("force", ms(0)),
+                ("post", ms(30)),
+                ("pre", ms(30)),
+                ("force", ms(30)),
+                ("post", ms(50)),
+            ]
+        );
+    }
+
+    #[test]
+    fn events() {
+        let mut log = Log::default();
+        let mut runner = Runner::new(state(), Integrator::RungeKutta4, Duration::from_millis(100))
+            .with_events(Events::new().with(Event::ground_impact()))
+            .observe(&mut log);
+
+        let event = runner.run_until(Duration::from_secs(10)).unwrap();
+        assert_eq!(runner.time, event.time);
+        let steps = runner.steps as usize;
+        drop(runner);
+
+        // The trial steps locating the impact are not observed
+        assert_eq!(log.stages.len(), steps * 4);
+        assert_eq!(log.calls[log.calls.len() - 2], ("event", event.time));
+        assert_eq!(log.calls[log.calls.len() - 1], ("post", event.time));
+        assert!(log.stages.last().unwrap().offset < Duration::from_millis(100));
+    }
+
+    /// Cancels the spin of the body before every step
+    struct Despin;
+
+    impl Observer for Despin {
+        fn pre_step(&mut self, _: Duration, _: usize, state: &mut State) {
+            state.momentum.angular = Default::default();
+        }
+    }
+
+    #[test]
+    fn controllers() {
+        let mut runner = Runner::new(state(), Integrator::RungeKutta4, Duration::from_millis(10))
+            .observe(Despin);
+        runner.run_until(Duration::from_millis(100));
+
+        assert_eq!(runner.steps, 10);
+        assert_eq!(runner.time, Duration::from_millis(100));
+
+        let mut free = state();
+        for _ in 0..10 {
+            free.runge_kutta_4(Duration::from_millis(10));
+        }
+        assert!(runner.state.velocity().angular.0.length() < free.velocity().angular.0.length());
+    }
+
+    #[test]
+    #[should_panic = "step must not be zero"]
+    fn zero_step() {
+        let _ = Runner::new(state(), Integrator::RungeKutta4, Duration::ZERO);
+    }
+
+    #[cfg(feature = "scenario")]
+    mod simulation {
+        use super::*;
+        use crate::scenario::{EndCondition, Simulation};
+        use alloc::string::ToString;
+        use alloc::vec;
+
+        fn simulation() -> Simulation {
+            let mut high = state();
+            high.transform.translation.0.z = 20.;
+
+            Simulation {
+                names: vec!["high".to_string(), "low".to_string()],
+                states: vec![high, state()],
+                integrator: Integrator::RungeKutta4,
+                step: Duration::from_millis(100),
+                end: EndCondition::Time(10.),
+                time: Duration::ZERO,
+                steps: 0,
+            }
+        }
+
+        #[test]
+        fn matches_step() {
+            let mut sim = simulation();
+            let mut observed = simulation();
+            let mut log = Log::default();
+
+            sim.run();
+            assert_eq!(observed.run_observed(&mut [], &mut log), None);
+            assert_eq!(observed, sim);
+            assert_eq!(log.stages.len(), 2 * 4 * 100);
+        }
+
+        #[test]
+        fn events_stop_every_body() {
+            let mut sim = simulation();
+            let mut events = [Events::new(), Events::new().with(Event::ground_impact())];
+            let mut log = Log::default();
+
+            let event = sim.run_observed(&mut events, &mut log).unwrap();
+            assert_eq!((event.body, event.index), (1, 0));
+            assert_eq!(sim.time, event.time);
+            assert!(sim.states[1].transform.translation.0.z <= 0.);
+            assert!(sim.states[0].transform.translation.0.z > 5.);
+            assert_eq!(log.calls.last(), Some(&("post", event.time)));
+        }
+    }
+}
@@ -0,0 +1,198 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "approx")]
+use {
+    approx::{AbsDiffEq, RelativeEq, UlpsEq},
+    approx_derive::Approx,
+};
+
+use crate::overload::overload;
+use crate::{
+    frame::{Body, Framed, Point, World},
+    momentum::{AngMom, LinMom, Momentum},
+    State,
+};
+use core::iter::Sum;
+use glam::DVec3 as Vec3;
+
+/// An instantaneous change in momentum, with linear and angular components.
+///
+/// Used for events too short to be resolved by the integrator, such as separation, ejection
+/// charges and collisions. Where a [Moment](crate::moments::Moment) changes [Momentum] over
+/// time, an [Impulse] changes it immediately.
+#[cfg_attr(feature = "approx", derive(Approx))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Impulse {
+    /// Linear impulse component.
+    pub linear: LinMom,
+    /// Angular impulse component.
+    pub angular: AngMom,
+}
+
+impl Impulse {
+    /// Zero impulse constant.
+    pub const ZERO: Self = Self::new(LinMom::ZERO, AngMom::ZERO);
+
+    /// Constructs a new [Impulse] from given linear and angular components.
+    #[inline]
+    #[must_use]
+    pub const fn new(linear: LinMom, angular: AngMom) -> Self {
+        Self { linear, angular }
+    }
+
+    /// Creates an impulse with only a linear component.
+    #[inline]
+    #[must_use]
+    pub const fn from_linear(linear: LinMom) -> Self {
+        Self::new(linear, AngMom::ZERO)
+    }
+
+    /// Creates an impulse with only an angular component.
+    #[inline]
+    #[must_use]
+    pub const fn from_angular(angular: AngMom) -> Self {
+        Self::new(LinMom::ZERO, angular)
+    }
+
+    /// Constructs an [Impulse] from raw vector representations of its components.
+    #[inline]
+    #[must_use]
+    pub const fn from_vec3s(linear: Vec3, angular: Vec3) -> Self {
+        Self::new(LinMom::from_vec3(linear), AngMom::from_vec3(angular))
+    }
+
+    /// Constructs an [Impulse] from a linear impulse and an offset, computing the angular
+    /// impulse as their cross product.
+    #[inline]
+    #[must_use]
+    pub fn from_linear_and_offset(linear: LinMom, offset: Vec3) -> Self {
+        let angular = AngMom::from_vec3(offset.cross(linear.0));
+        Self::new(linear, angular)
+    }
+}
+
+/// Impulse application
+impl State {
+    /// Applies an impulse acting through the centre of mass, expressed in the world frame.
+    pub fn apply_impulse(&mut self, impulse: &Impulse) {
+        self.momentum += Momentum::from(*impulse);
+    }
+
+    /// Applies a world-frame linear impulse at a point in the world.
+    pub fn apply_impulse_at(&mut self, impulse: Framed<LinMom, World>, point: Point<World>) {
+        let offset = point.0 - self.transform.translation.0;
+        self.apply_impulse(&Impulse::from_linear_and_offset(impulse.0, offset));
+    }
+
+    /// Applies a body-frame linear impulse at a point on the body.
+    pub fn apply_body_impulse_at(&mut self, impulse: Framed<LinMom, Body>, point: Point<Body>) {
+        let impulse = self.to_world(impulse);
+        let point = self.point_to_world(point);
+        self.apply_impulse_at(impulse, point);
+    }
+}
+
+/// Conversion between [Impulse] and the [Momentum] it adds.
+impl From<Impulse> for Momentum {
+    #[inline]
+    fn from(value: Impulse) -> Self {
+        Self::new(value.linear, value.angular)
+    }
+}
+
+impl From<Momentum> for Impulse {
+    #[inline]
+    fn from(value: Momentum) -> Self {
+        Self::new(value.linear, value.angular)
+    }
+}
+
+/// Implements summation over an iterator of [Impulse] values.
+impl Sum for Impulse {
+    #[inline]
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |a, b| a + b)
+    }
+}
+
+overload!((a: ?Impulse) + (b: ?Impulse) -> Impulse { Impulse::new(a.linear + b.linear, a.angular + b.angular) });
+overload!((a: ?Impulse) - (b: ?Impulse) -> Impulse { Impulse::new(a.linear - b.linear, a.angular - b.angular) });
+overload!((a: &mut Impulse) += (b: ?Impulse) { a.linear += b.linear; a.angular += b.angular; });
+overload!((a: &mut Impulse) -= (b: ?Impulse) { a.linear -= b.linear; a.angular -= b.angular; });
+
+overload!((a: ?Impulse) * (b: f64) -> Impulse { Impulse::new(a.linear * b, a.angular * b) });
+overload!((a: ?Impulse) / (b: f64) -> Impulse { Impulse::new(a.linear / b, a.angular / b) });
+overload!((a: &mut Impulse) *= (b: f64) { a.linear *= b; a.angular *= b; });
+overload!((a: &mut Impulse) /= (b: f64) { a.linear /= b; a.angular /= b; });
+
+overload!((a: &mut Momentum) += (b: ?Impulse) { a.linear += b.linear; a.angular += b.angular; });
+
+overload!(-(a: ?Impulse) -> Impulse { Impulse::new(-a.linear, -a.angular) });
+
+#[cfg(test)]
+mod apply {
+    use super::*;
+    use crate::{
+        inertia_mass::{Inertia, InertiaMass, Mass},
+        moments::{Force, Moment},
+        transform::{Rotation, Transform, Translation},
+        StateBuilder,
+    };
+    use approx::assert_ulps_eq;
+    use std::f64::consts::FRAC_PI_2;
+
+    fn state() -> State {
+        StateBuilder::new()
+            .mass(InertiaMass::new(
+                Mass::new(2.),
+                Inertia::cylinder_x(1., 0.1, 2.),
+            ))
+            .transform(Transform::new(
+                Translation::new(5., 0., 0.),
+                Rotation::from_z(FRAC_PI_2),
+            ))
+            .build()
+    }
+
+    #[test]
+    fn mirrors_moment() {
+        let offset = Vec3::new(0.5, -1., 2.);
+        let force = Force::new(3., 1., -2.);
+
+        let impulse = Impulse::from_linear_and_offset(LinMom(force.0), offset);
+        let moment = Moment::from_force_and_offset(force, offset);
+
+        assert_ulps_eq!(impulse.angular.0, moment.torque.0);
+    }
+
+    #[test]
+    fn through_centre() {
+        let mut s = state();
+        s.apply_impulse(&Impulse::from_linear(LinMom::X));
+
+        assert_ulps_eq!(s.momentum, Momentum::from_linear(LinMom::X));
+    }
+
+    #[test]
+    fn world_point() {
+        let mut s = state();
+        let point = Point::new(Vec3::new(5., 1., 0.));
+        s.apply_impulse_at(Framed::new(LinMom::X), point);
+
+        assert_ulps_eq!(s.momentum, Momentum::new(LinMom::X, AngMom::NEG_Z));
+    }
+
+    #[test]
+    fn body_point() {
+        let mut s = state();
+        // Body x is world y, so a body-frame push along y at the nose turns the body about z
+        s.apply_body_impulse_at(Framed::new(LinMom::Y), Point::new(Vec3::X));
+
+        assert_ulps_eq!(
+            s.momentum,
+            Momentum::new(LinMom::NEG_X, AngMom::Z),
+            epsilon = 1e-15
+        );
+    }
+}
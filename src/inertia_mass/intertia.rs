@@ -6,7 +6,14 @@ use {
     approx_derive::Approx,
 };
 
-use glam::{DMat3 as Mat3, DQuat as Quat};
+use crate::math;
+use crate::{quantity::common_ops, transform::Rotation};
+use glam::{DMat3 as Mat3, DQuat as Quat, DVec3 as Vec3};
+
+/// Upper bound on the number of Jacobi sweeps used by [Inertia::principal_axes].
+///
+/// A symmetric 3x3 matrix converges quadratically, so this is never reached in practice.
+const MAX_JACOBI_SWEEPS: usize = 50;
 
 /// The mass distribution of an object.
 ///
@@ -18,6 +25,12 @@ use glam::{DMat3 as Mat3, DQuat as Quat};
 pub struct Inertia(pub Mat3);
 
 impl Inertia {
+    /// The inertia of a point mass at the origin, or of no mass at all.
+    pub const ZERO: Self = Self::new(Mat3::ZERO);
+
+    /// Creates a new [Inertia] from a tensor in kg·m².
+    #[inline]
+    #[must_use]
     pub const fn new(inertia: Mat3) -> Self {
         Self(inertia)
     }
@@ -123,6 +136,125 @@ impl Inertia {
     }
 }
 
+impl Inertia {
+    /// Creates a diagonal inertia tensor from its principal moments.
+    ///
+    /// # Arguments
+    /// * `moments` - The moments of inertia around the x, y and z axes.
+    ///
+    /// # Returns
+    /// An [Inertia] with `moments` on the diagonal and zero products of inertia.
+    #[inline]
+    #[must_use]
+    pub const fn from_diagonal(moments: Vec3) -> Self {
+        Self::new(Mat3::from_diagonal(moments))
+    }
+
+    /// Creates an inertia tensor from principal moments and the orientation of the principal axes.
+    ///
+    /// This is the inverse of [Inertia::principal_axes].
+    ///
+    /// # Arguments
+    /// * `moments` - The principal moments of inertia.
+    /// * `axes` - Rotation taking the principal frame into the frame of the tensor.
+    ///
+    /// # Returns
+    /// An [Inertia] equal to `R·diag(moments)·Rᵀ`.
+    #[inline]
+    #[must_use]
+    pub fn from_principal(moments: Vec3, axes: Rotation) -> Self {
+        Self::from_diagonal(moments).rotated(axes.0)
+    }
+
+    /// Returns the moments of inertia around the x, y and z axes, i.e. the diagonal of the tensor.
+    #[inline]
+    #[must_use]
+    pub fn diagonal(&self) -> Vec3 {
+        Vec3::new(self.0.x_axis.x, self.0.y_axis.y, self.0.z_axis.z)
+    }
+
+    /// Returns `true` if all products of inertia are exactly zero.
+    ///
+    /// A diagonal tensor is already expressed in its principal frame.
+    #[inline]
+    #[must_use]
+    pub fn is_diagonal(&self) -> bool {
+        let m = self.0;
+        m.x_axis.y == 0.0
+            && m.x_axis.z == 0.0
+            && m.y_axis.x == 0.0
+            && m.y_axis.z == 0.0
+            && m.z_axis.x == 0.0
+            && m.z_axis.y == 0.0
+    }
+
+    /// Decomposes the tensor into principal moments and principal axes.
+    ///
+    /// Uses the cyclic Jacobi eigenvalue method, which is exact for symmetric matrices and only
+    /// needs a handful of sweeps for a 3x3 tensor. Diagonal tensors are returned as is with an
+    /// identity rotation.
+    ///
+    /// # Returns
+    /// The principal moments and a [Rotation] whose matrix has the matching principal axes as
+    /// columns, such that the tensor equals `R·diag(moments)·Rᵀ`.
+    #[must_use]
+    pub fn principal_axes(&self) -> (Vec3, Rotation) {
+        if self.is_diagonal() {
+            return (self.diagonal(), Rotation::ZERO);
+        }
+
+        let mut a = self.0.to_cols_array_2d();
+        let mut v = Mat3::IDENTITY.to_cols_array_2d();
+
+        let norm = self.0.to_cols_array().iter().map(|e| e * e).sum::<f64>();
+        let tolerance = norm * f64::EPSILON * f64::EPSILON;
+
+        for _ in 0..MAX_JACOBI_SWEEPS {
+            let off = a[0][1] * a[0][1] + a[0][2] * a[0][2] + a[1][2] * a[1][2];
+            if off <= tolerance {
+                break;
+            }
+
+            for (p, q) in [(0, 1), (0, 2), (1, 2)] {
+                if a[p][q] == 0.0 {
+                    continue;
+                }
+
+                let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+                let t = theta.signum() / (theta.abs() + math::sqrt(theta * theta + 1.0));
+                let c = 1.0 / math::sqrt(t * t + 1.0);
+                let s = t * c;
+
+                for col in a.iter_mut() {
+                    let (kp, kq) = (col[p], col[q]);
+                    col[p] = c * kp - s * kq;
+                    col[q] = s * kp + c * kq;
+                }
+                let (row_p, row_q) = (a[p], a[q]);
+                for (k, (pk, qk)) in row_p.into_iter().zip(row_q).enumerate() {
+                    a[p][k] = c * pk - s * qk;
+                    a[q][k] = s * pk + c * qk;
+                }
+                for col in v.iter_mut() {
+                    let (kp, kq) = (col[p], col[q]);
+                    col[p] = c * kp - s * kq;
+                    col[q] = s * kp + c * kq;
+                }
+            }
+        }
+
+        // `v` is stored with rows and columns swapped, so transpose it back to get the
+        // eigenvectors as columns.
+        let mut axes = Mat3::from_cols_array_2d(&v).transpose();
+        if axes.determinant() < 0.0 {
+            axes.z_axis = -axes.z_axis;
+        }
+
+        let moments = Vec3::new(a[0][0], a[1][1], a[2][2]);
+        (moments, Rotation::new(Quat::from_mat3(&axes)))
+    }
+}
+
 impl From<Mat3> for Inertia {
     fn from(value: Mat3) -> Self {
         Self::new(value)
@@ -134,3 +266,61 @@ impl From<Inertia> for Mat3 {
         value.0
     }
 }
+
+// Inertia tensors about the same point add up, which allows building composite bodies
+common_ops!(Inertia, f64);
+
+#[cfg(test)]
+mod principal_axes {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn diagonal() {
+        let inertia = Inertia::cylinder_z(2.0, 0.5, 3.0);
+        let (moments, axes) = inertia.principal_axes();
+
+        assert!(inertia.is_diagonal());
+        assert_relative_eq!(moments, inertia.diagonal());
+        assert_relative_eq!(axes.0, Quat::IDENTITY);
+    }
+
+    #[test]
+    fn rotated_cylinder() {
+        let inertia = Inertia::cylinder_x(4.0, 0.3, 2.0);
+        let rot = Quat::from_rotation_z(0.4) * Quat::from_rotation_x(1.1);
+        let rotated = inertia.rotated(rot);
+
+        let (moments, axes) = rotated.principal_axes();
+        let mut sorted = moments.to_array();
+        let mut expected = inertia.diagonal().to_array();
+        sorted.sort_by(f64::total_cmp);
+        expected.sort_by(f64::total_cmp);
+
+        assert!(!rotated.is_diagonal());
+        assert_relative_eq!(Vec3::from(sorted), Vec3::from(expected), epsilon = 1e-12);
+        assert_relative_eq!(
+            Inertia::from_principal(moments, axes).0,
+            rotated.0,
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn general_tensor() {
+        let inertia = Inertia::new(Mat3::from_cols_array_2d(&[
+            [4.0, -1.0, 0.5],
+            [-1.0, 3.0, -0.25],
+            [0.5, -0.25, 5.0],
+        ]));
+
+        let (moments, axes) = inertia.principal_axes();
+        let m = Mat3::from_quat(axes.0);
+
+        assert_relative_eq!(m.determinant(), 1.0, epsilon = 1e-12);
+        assert_relative_eq!(moments.element_sum(), 12.0, epsilon = 1e-12);
+        for (i, axis) in [m.x_axis, m.y_axis, m.z_axis].into_iter().enumerate() {
+            assert_relative_eq!(inertia.0 * axis, axis * moments[i], epsilon = 1e-12);
+        }
+    }
+}
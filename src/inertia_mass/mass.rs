@@ -1,6 +1,8 @@
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+use crate::quantity::scalar_ops;
+
 /// The mass an object.
 ///
 /// This struct only exists to allow strongly typed equations with mass to be possible.
@@ -9,21 +11,15 @@ use serde::{Deserialize, Serialize};
 pub struct Mass(pub f64);
 
 impl Mass {
+    /// Zero mass constant.
     pub const ZERO: Self = Self::new(0.);
 
+    /// Creates a new [Mass] from a value in kilograms.
+    #[inline]
+    #[must_use]
     pub const fn new(mass: f64) -> Self {
         Self(mass)
     }
 }
 
-impl From<f64> for Mass {
-    fn from(value: f64) -> Self {
-        Self::new(value)
-    }
-}
-
-impl From<Mass> for f64 {
-    fn from(value: Mass) -> Self {
-        value.0
-    }
-}
+scalar_ops!(Mass, f64);
@@ -22,23 +22,49 @@ pub struct InertiaMass {
 }
 
 impl InertiaMass {
+    /// Creates an [InertiaMass], inverting the inertia tensor
+    ///
+    /// A singular or non-finite tensor has no inverse, its inverse is then left as NaN for
+    /// [StateBuilder::try_build](crate::StateBuilder::try_build) to reject.
     pub fn new(mass: Mass, inertia: Inertia) -> Self {
+        let determinant = inertia.0.determinant();
+        let inv_inertia = if determinant.is_finite() && determinant != 0. {
+            inertia.0.inverse()
+        } else {
+            Mat3::NAN
+        };
+
         Self {
             mass,
             inertia,
-            inv_inertia: Inertia(inertia.0.inverse()),
+            inv_inertia: Inertia(inv_inertia),
         }
     }
 
     /// Rotates the mass using a [Quat]
     ///
-    /// If performance is critical, directly calling [InertiaMass::rot_mat] may be preferable
+    /// Both tensors are rotated as `R·I·Rᵀ`, so no matrix inversion takes place. If performance is
+    /// critical, directly calling [InertiaMass::rot_mat] may be preferable
     pub fn rotated(&self, rot: Quat) -> Self {
-        Self::new(self.mass, self.inertia.rotated(rot))
+        self.rot_mat(Mat3::from_quat(rot))
     }
 
     /// Rotates the mass using a [Mat3]
     pub fn rot_mat(&self, rot: Mat3) -> Self {
-        Self::new(self.mass, self.inertia.rot_mat(rot))
+        Self {
+            mass: self.mass,
+            inertia: self.inertia.rot_mat(rot),
+            inv_inertia: self.inv_inertia.rot_mat(rot),
+        }
+    }
+
+    /// Returns the inverse inertia tensor rotated by a [Mat3]
+    ///
+    /// Cheaper than [InertiaMass::rot_mat] when only the inverse tensor is needed, such as when
+    /// converting angular momentum into angular velocity
+    #[inline]
+    #[must_use]
+    pub fn rotated_inv_inertia(&self, rot: &Mat3) -> Inertia {
+        self.inv_inertia.rot_mat(*rot)
     }
 }
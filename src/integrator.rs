@@ -0,0 +1,132 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use core::{error::Error, fmt, str::FromStr, time::Duration};
+
+use crate::batch::{self, Batch};
+use crate::dynamics::Dynamics;
+use crate::hooks::Stage;
+use crate::State;
+
+/// The numerical method used to step a [State] forward
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Integrator {
+    /// [State::forward_euler]
+    #[cfg_attr(feature = "serde", serde(rename = "forward_euler"))]
+    ForwardEuler,
+    /// [State::runge_kutta_4]
+    #[default]
+    #[cfg_attr(feature = "serde", serde(rename = "runge_kutta_4"))]
+    RungeKutta4,
+}
+
+impl Integrator {
+    /// Steps a single state forward by a [Duration]
+    pub fn step(self, state: &mut State, delta: Duration) {
+        match self {
+            Self::ForwardEuler => state.forward_euler(delta),
+            Self::RungeKutta4 => state.runge_kutta_4(delta),
+        }
+    }
+
+    /// Steps a single state forward by a [Duration], passing every evaluation of the panels to
+    /// `observe`
+    ///
+    /// The result is bit-for-bit the same as [Integrator::step].
+    pub fn step_observed(self, state: &mut State, delta: Duration, observe: impl FnMut(&Stage)) {
+        let dynamics = Dynamics::new(&state.mass, &state.panels);
+        let (transform, momentum) = (&mut state.transform, &mut state.momentum);
+        let renormalization = &mut state.renormalization;
+
+        match self {
+            Self::ForwardEuler => {
+                dynamics.forward_euler_observed(
+                    transform,
+                    momentum,
+                    renormalization,
+                    delta,
+                    observe,
+                );
+            }
+            Self::RungeKutta4 => {
+                dynamics.runge_kutta_4_observed(
+                    transform,
+                    momentum,
+                    renormalization,
+                    delta,
+                    observe,
+                );
+            }
+        }
+    }
+
+    /// Steps every state forward by a [Duration], see [batch::runge_kutta_4_all]
+    pub fn step_all(self, states: &mut [State], delta: Duration) {
+        match self {
+            Self::ForwardEuler => batch::forward_euler_all(states, delta),
+            Self::RungeKutta4 => batch::runge_kutta_4_all(states, delta),
+        }
+    }
+
+    /// Steps every entity of a [Batch] forward by a [Duration]
+    pub fn step_batch(self, batch: &mut Batch, delta: Duration) {
+        match self {
+            Self::ForwardEuler => batch.forward_euler(delta),
+            Self::RungeKutta4 => batch.runge_kutta_4(delta),
+        }
+    }
+}
+
+impl fmt::Display for Integrator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ForwardEuler => write!(f, "forward_euler"),
+            Self::RungeKutta4 => write!(f, "runge_kutta_4"),
+        }
+    }
+}
+
+/// Returned when parsing the name of an unknown [Integrator]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseIntegratorError;
+
+impl fmt::Display for ParseIntegratorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown integrator, expected `forward_euler` or `runge_kutta_4`"
+        )
+    }
+}
+
+impl Error for ParseIntegratorError {}
+
+impl FromStr for Integrator {
+    type Err = ParseIntegratorError;
+
+    /// Parses the names used by [Integrator]'s `Display`, as well as `euler` and `rk4`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "forward_euler" | "euler" => Ok(Self::ForwardEuler),
+            "runge_kutta_4" | "rk4" => Ok(Self::RungeKutta4),
+            _ => Err(ParseIntegratorError),
+        }
+    }
+}
+
+#[cfg(test)]
+mod names {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn round_trip() {
+        for integrator in [Integrator::ForwardEuler, Integrator::RungeKutta4] {
+            assert_eq!(integrator.to_string().parse(), Ok(integrator));
+        }
+        assert_eq!("rk4".parse(), Ok(Integrator::RungeKutta4));
+        assert_eq!("euler".parse(), Ok(Integrator::ForwardEuler));
+        assert_eq!("midpoint".parse::<Integrator>(), Err(ParseIntegratorError));
+    }
+}
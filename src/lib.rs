@@ -1,24 +1,66 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("either the `std` or the `libm` feature must be enabled");
+
+extern crate alloc;
+
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use std::time::Duration;
+use alloc::vec::Vec;
+use core::time::Duration;
+
+use glam::DMat3 as Mat3;
 
+use acceleration::{Acceleration, LinAcc};
+use dynamics::Dynamics;
+use energy::{Energy, Power};
+use frame::{Body, Framed, Point, Rotate, World};
 use inertia_mass::InertiaMass;
 use moments::Moment;
 use momentum::Momentum;
-use panels::Panel;
+use panels::{AeroDiagnostics, Panel, PanelDiagnostics, StabilityPoint};
+use renormalization::Renormalization;
 use transform::Transform;
 
+pub mod acceleration;
+pub mod batch;
+#[cfg(feature = "checkpoint")]
+pub mod checkpoint;
+pub mod energy;
+pub mod events;
+pub mod frame;
+pub mod hooks;
+pub mod impulse;
 pub mod inertia_mass;
+pub mod integrator;
 pub mod moments;
 pub mod momentum;
 pub mod panels;
+#[cfg(feature = "recording")]
+pub mod recording;
+pub mod renormalization;
+#[cfg(feature = "replay")]
+pub mod replay;
+#[cfg(feature = "scenario")]
+pub mod scenario;
+#[cfg(feature = "f32")]
+pub mod single;
 pub mod transform;
 pub mod velocity;
 
 mod builder;
-pub use builder::StateBuilder;
-use velocity::Velocity;
+mod dynamics;
+#[cfg(test)]
+mod fixtures;
+mod math;
+#[cfg(feature = "serde")]
+mod non_finite;
+mod overload;
+mod quantity;
+pub use builder::{BuildError, StateBuilder};
+use velocity::{LinVel, Velocity};
 
 /// Represents the kinetic state of a simulated entity
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -28,6 +70,9 @@ pub struct State {
     pub transform: Transform,
     pub momentum: Momentum,
     pub panels: Vec<Panel>,
+    /// How the rotation is kept at unit length while stepping
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub renormalization: Renormalization,
 }
 
 impl State {
@@ -42,35 +87,236 @@ impl State {
             transform,
             momentum,
             panels,
+            renormalization: Renormalization::DEFAULT,
         }
     }
 
     pub fn panel_moment(&self) -> Moment {
+        let vel = self.velocity();
+        self.panel_moment_at(&vel)
+    }
+
+    /// Returns the velocity of the entity, expressed in the world frame
+    pub fn velocity(&self) -> Velocity {
+        self.velocity_at(&self.rotation_matrix())
+    }
+
+    /// Returns the linear and angular acceleration caused by the panels, expressed in the world
+    /// frame
+    ///
+    /// The angular part includes the gyroscopic `ω × I·ω` term
+    pub fn acceleration(&self) -> Acceleration {
+        let rot = self.rotation_matrix();
+        let vel = self.velocity_at(&rot);
+        let moment = self.panel_moment_at(&vel);
+
+        self.mass.rot_mat(rot).acceleration(&moment, &vel.angular)
+    }
+
+    /// Returns the rotation of the entity as a [Mat3]
+    ///
+    /// Computed once per evaluation and shared between everything that needs it
+    fn rotation_matrix(&self) -> Mat3 {
+        Dynamics::rotation_matrix(&self.transform)
+    }
+
+    /// Computes the velocity using an already computed rotation matrix
+    fn velocity_at(&self, rot: &Mat3) -> Velocity {
+        self.dynamics().velocity_at(&self.momentum, rot)
+    }
+
+    /// Computes the panel moment using an already computed velocity
+    fn panel_moment_at(&self, vel: &Velocity) -> Moment {
+        self.dynamics().panel_moment_at(&self.transform, vel)
+    }
+
+    fn dynamics(&self) -> Dynamics<'_> {
+        Dynamics::new(&self.mass, &self.panels)
+    }
+}
+
+/// Energy and power
+impl State {
+    /// Returns the kinetic energy of the translational motion, `|p|² / 2m`
+    #[must_use]
+    pub fn translational_kinetic_energy(&self) -> Energy {
+        Energy(self.momentum.linear.0.length_squared() / (2. * self.mass.mass.0))
+    }
+
+    /// Returns the kinetic energy of the rotational motion, `L·ω / 2`
+    #[must_use]
+    pub fn rotational_kinetic_energy(&self) -> Energy {
+        let ang_vel = self.velocity().angular;
+        Energy(self.momentum.angular.0.dot(ang_vel.0) / 2.)
+    }
+
+    /// Returns the total kinetic energy, translational and rotational
+    #[must_use]
+    pub fn kinetic_energy(&self) -> Energy {
+        self.translational_kinetic_energy() + self.rotational_kinetic_energy()
+    }
+
+    /// Returns the potential energy in a uniform gravity field, relative to the world origin
+    ///
+    /// # Arguments
+    /// * `gravity` - The gravitational acceleration, e.g. `LinAcc::with_z(-9.81)`
+    #[must_use]
+    pub fn potential_energy(&self, gravity: &LinAcc) -> Energy {
+        Energy(-self.mass.mass.0 * gravity.0.dot(self.transform.translation.0))
+    }
+
+    /// Returns the power delivered by each panel, in the same order as [State::panels]
+    ///
+    /// Negative values mean the panel is removing energy from the entity, as drag does
+    #[must_use]
+    pub fn panel_powers(&self) -> Vec<Power> {
+        let vel = self.velocity();
         let rot = self.transform.rotation.0;
-        let vel = self.momentum / self.mass.rotated(rot);
 
         self.panels
             .iter()
-            .map(|panel| panel.to_moment(&vel, &rot))
-            .fold(Moment::ZERO, |acc, e| acc + e)
+            .map(|panel| panel.to_moment(&vel, &rot).power(&vel))
+            .collect()
     }
 
-    pub fn velocity(&self) -> Velocity {
-        self.momentum / self.mass.rotated(self.transform.rotation.0)
+    /// Returns the total power delivered by all panels
+    #[must_use]
+    pub fn panel_power(&self) -> Power {
+        self.panel_moment().power(&self.velocity())
+    }
+}
+
+/// Aerodynamic diagnostics
+impl State {
+    /// Returns what each panel contributes to the aerodynamics, in the same order as
+    /// [State::panels]
+    #[must_use]
+    pub fn panel_diagnostics(&self) -> Vec<PanelDiagnostics> {
+        let vel = self.velocity();
+        let rot = self.transform.rotation.0;
+
+        self.panels
+            .iter()
+            .map(|panel| panel.diagnose(&vel, &rot))
+            .collect()
+    }
+
+    /// Returns the contribution of each panel along with the dynamic pressure, angle of attack,
+    /// sideslip and centre of pressure of the entity
+    #[must_use]
+    pub fn aerodynamics(&self) -> AeroDiagnostics {
+        let vel = self.velocity();
+        AeroDiagnostics::new(
+            self.panel_diagnostics(),
+            self.panel_moment_at(&vel),
+            vel.linear,
+            &self.transform.rotation.0,
+        )
+    }
+
+    /// Sweeps the angle of attack and locates the centre of pressure of the panels at each
+    /// angle, along with the static margin in calibers of diameter `caliber`
+    ///
+    /// The analysis is done in the body frame, with `x` pointing forward and the centre of mass
+    /// at the origin. The current rotation and velocity of the entity are ignored: the flow comes
+    /// from straight ahead, tilted by the angle of attack, and the entity doesn't rotate. The
+    /// speed of the flow only scales the forces, not where they act.
+    ///
+    /// The centre of pressure is where the normal force acts along the `x` axis. The axial force
+    /// of panels offset along `z` is left out, as it doesn't depend on where the panel sits along
+    /// the body. The static margin is `None` unless `caliber` is positive and finite.
+    #[must_use]
+    pub fn stability(
+        &self,
+        angles: impl IntoIterator<Item = f64>,
+        caliber: f64,
+    ) -> Vec<StabilityPoint> {
+        let valid_caliber = caliber.is_finite() && caliber > 0.;
+
+        angles
+            .into_iter()
+            .map(|angle| {
+                let flow = LinVel::new(math::cos(angle), 0., math::sin(angle));
+
+                // Each panel's normal force `F` at `x` on the body axis pitches it by `-x·F`
+                let (normal, pitch) =
+                    self.panels.iter().fold((0., 0.), |(normal, pitch), panel| {
+                        let force = panel.to_force(&flow).0.z;
+                        (normal + force, pitch - panel.offset.x * force)
+                    });
+                let centre_of_pressure = (normal != 0.).then(|| -pitch / normal);
+
+                StabilityPoint {
+                    angle_of_attack: angle,
+                    centre_of_pressure,
+                    static_margin: centre_of_pressure
+                        .filter(|_| valid_caliber)
+                        .map(|x| -x / caliber),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Frame conversions
+impl State {
+    /// Expresses a body-frame quantity in the world frame
+    #[inline]
+    #[must_use]
+    pub fn to_world<T: Rotate>(&self, value: Framed<T, Body>) -> Framed<T, World> {
+        self.transform.to_world(value)
+    }
+
+    /// Expresses a world-frame quantity in the body frame
+    #[inline]
+    #[must_use]
+    pub fn to_body<T: Rotate>(&self, value: Framed<T, World>) -> Framed<T, Body> {
+        self.transform.to_body(value)
+    }
+
+    /// Converts a position relative to the entity into a position in the world
+    #[inline]
+    #[must_use]
+    pub fn point_to_world(&self, point: Point<Body>) -> Point<World> {
+        self.transform.point_to_world(point)
+    }
+
+    /// Converts a position in the world into a position relative to the entity
+    #[inline]
+    #[must_use]
+    pub fn point_to_body(&self, point: Point<World>) -> Point<Body> {
+        self.transform.point_to_body(point)
+    }
+
+    /// Returns the velocity of the entity tagged as a world-frame quantity
+    #[must_use]
+    pub fn world_velocity(&self) -> Framed<Velocity, World> {
+        Framed::new(self.velocity())
+    }
+
+    /// Returns the velocity of the entity expressed in its own body frame
+    #[must_use]
+    pub fn body_velocity(&self) -> Framed<Velocity, Body> {
+        self.to_body(self.world_velocity())
     }
 }
 
 /// Time step functions
+///
+/// Both functions renormalize the rotation after every step according to
+/// [State::renormalization].
 impl State {
     /// Steps the state forward by a [Duration] using the Forward Euler method
     ///
     /// The Euler method is much simpler than Runge Kutta 4 and requires less compute per
     /// iteration, it does however result in more error over time and can become unstable easier
     pub fn forward_euler(&mut self, time: Duration) {
-        self.momentum += self.panel_moment() * time;
-
-        let velocity = self.momentum / self.mass.rotated(self.transform.rotation.0);
-        self.transform += velocity * time;
+        Dynamics::new(&self.mass, &self.panels).forward_euler(
+            &mut self.transform,
+            &mut self.momentum,
+            &mut self.renormalization,
+            time,
+        );
     }
 
     /// Steps the state forward by a [Duration] using the Runge Kutta 4 method
@@ -78,24 +324,243 @@ impl State {
     /// Runge Kutta 4 is a more robust way to step forward a simulation, compared to the Forward
     /// Euler method, it requires more compute, but the results are more accurate and stable.
     pub fn runge_kutta_4(&mut self, delta: Duration) {
-        let half_delta = delta / 2;
+        Dynamics::new(&self.mass, &self.panels).runge_kutta_4(
+            &mut self.transform,
+            &mut self.momentum,
+            &mut self.renormalization,
+            delta,
+        );
+    }
+}
+
+#[cfg(test)]
+mod time_step {
+    use super::*;
+    use crate::inertia_mass::{Inertia, Mass};
+    use crate::momentum::AngMom;
+    use approx::assert_relative_eq;
+    use glam::DVec3 as Vec3;
+
+    /// A body with a single drag panel at its centre moving along the panel normal, giving
+    /// `m·dv/dt = -k·v²` with the closed form solution `v(t) = v0 / (1 + k·v0·t / m)`
+    fn drag_body(v0: f64) -> (State, f64) {
+        let mass = 2.0;
+        let area = 0.1;
+        let state = StateBuilder::new()
+            .mass(InertiaMass::new(
+                Mass::new(mass),
+                Inertia::cylinder_x(1.0, 0.1, mass),
+            ))
+            .momentum(Momentum::from_linear_vec3(Vec3::X * v0 * mass))
+            .add_panel(Panel::new(Vec3::ZERO, Vec3::X, area))
+            .build();
+
+        let k = 1.293 * 1.28 / 2. * area;
+        (state, k / mass)
+    }
+
+    #[test]
+    fn runge_kutta_4_quadratic_drag() {
+        let v0 = 30.0;
+        let (mut state, k) = drag_body(v0);
+
+        for _ in 0..100 {
+            state.runge_kutta_4(Duration::from_millis(10));
+        }
+
+        let expected = v0 / (1.0 + k * v0);
+        assert_relative_eq!(state.velocity().linear.0.x, expected, epsilon = 1e-8);
+        assert_relative_eq!(
+            state.transform.translation.0.x,
+            (1.0 + k * v0).ln() / k,
+            epsilon = 1e-8
+        );
+    }
+
+    #[test]
+    fn renormalizes_rotation() {
+        let mut state = StateBuilder::new()
+            .mass(InertiaMass::new(
+                Mass::new(1.),
+                Inertia::cylinder_x(1.0, 0.1, 1.),
+            ))
+            .momentum(Momentum::from_angular_vec3(Vec3::new(0.3, 0.7, -0.2)))
+            .renormalization(Renormalization::new(0.))
+            .build();
+
+        for _ in 0..1000 {
+            state.runge_kutta_4(Duration::from_millis(10));
+        }
+
+        assert!(state.renormalization.count > 0);
+        assert_relative_eq!(state.transform.rotation.0.length(), 1., epsilon = 1e-15);
+    }
+
+    #[test]
+    fn energy_budget() {
+        let mut state = drag_body(30.0).0;
+        state.momentum.angular = AngMom::new(0.1, 0.2, 0.);
+        state.panels.push(Panel::new(Vec3::Y, Vec3::Z, 0.05));
+
+        let delta = Duration::from_micros(10);
+        let before = state.kinetic_energy();
+        let power = state.panel_power();
+        state.runge_kutta_4(delta);
+
+        let change = (state.kinetic_energy() - before) / delta;
+        assert_relative_eq!(change.0, power.0, max_relative = 1e-3);
+        assert!(power.0 < 0.);
+        assert_relative_eq!(
+            state.panel_powers().into_iter().sum::<Power>().0,
+            state.panel_power().0,
+            max_relative = 1e-12
+        );
+    }
+
+    #[test]
+    fn potential_energy() {
+        let mut state = drag_body(0.).0;
+        state.transform.translation.0.z = 10.;
+
+        let gravity = LinAcc::with_z(-9.81);
+        assert_relative_eq!(state.potential_energy(&gravity).0, 2. * 9.81 * 10.);
+    }
 
-        let mut int = self.clone();
-        let (k1_x, k1_p) = (int.velocity(), self.panel_moment());
-        int.momentum += k1_p * half_delta;
-        int.transform += k1_x * half_delta;
+    #[test]
+    fn forward_euler_quadratic_drag() {
+        let v0 = 30.0;
+        let (mut state, k) = drag_body(v0);
 
-        let (k2_x, k2_p) = (int.velocity(), self.panel_moment());
-        int.momentum += k2_p * half_delta;
-        int.transform += k2_x * half_delta;
+        for _ in 0..1000 {
+            state.forward_euler(Duration::from_millis(1));
+        }
+
+        let expected = v0 / (1.0 + k * v0);
+        assert_relative_eq!(state.velocity().linear.0.x, expected, epsilon = 1e-2);
+    }
+}
+
+#[cfg(test)]
+mod aerodynamics {
+    use super::*;
+    use crate::transform::Rotation;
+    use approx::assert_relative_eq;
+    use glam::DVec3 as Vec3;
+
+    /// A rocket along `x` with tail fins and a nose panel, flying at `velocity` in its body frame
+    fn rocket(velocity: Vec3, rotation: Rotation) -> State {
+        fixtures::rocket()
+            .transform(Transform::from_rotation(rotation))
+            .momentum(Momentum::from_vec3s(
+                rotation.0 * velocity * 2.,
+                Vec3::new(0., 0.01, 0.02),
+            ))
+            .add_panel(fixtures::nose())
+            .build()
+    }
+
+    #[test]
+    fn panel_breakdown() {
+        let state = rocket(Vec3::new(30., 2., 3.), Rotation::from_y(0.2));
+        let aero = state.aerodynamics();
+
+        let vel = state.velocity();
+        let rot = state.transform.rotation.0;
+        for (panel, diagnostics) in state.panels.iter().zip(&aero.panels) {
+            assert_eq!(diagnostics.moment, panel.to_moment(&vel, &rot));
+            assert_eq!(
+                diagnostics.force,
+                panel.rotated(&rot).to_force(&diagnostics.tip_velocity)
+            );
+        }
+
+        let sum = aero
+            .panels
+            .iter()
+            .fold(Moment::ZERO, |acc, panel| acc + panel.moment);
+        assert_eq!(sum, state.panel_moment());
+        assert_eq!(aero.moment, state.panel_moment());
+        assert_eq!(aero.panels, state.panel_diagnostics());
+    }
+
+    #[test]
+    fn flow_angles() {
+        for rotation in [Rotation::ZERO, Rotation::from_z(1.3)] {
+            let aero = rocket(Vec3::new(30., 2., 3.), rotation).aerodynamics();
+
+            assert_relative_eq!(aero.dynamic_pressure, 1.293 * 913. / 2., epsilon = 1e-10);
+            assert_relative_eq!(aero.angle_of_attack, 3f64.atan2(30.), epsilon = 1e-12);
+            assert_relative_eq!(aero.sideslip, (2. / 913f64.sqrt()).asin(), epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn centre_of_pressure() {
+        let mut state = rocket(Vec3::new(30., 0., 3.), Rotation::from_x(0.4));
+        state.momentum.angular = Default::default();
+        state.panels.truncate(2);
+
+        let cop = state.aerodynamics().centre_of_pressure.unwrap();
+        assert_relative_eq!(cop.0, Vec3::new(-0.5, 0., 0.), epsilon = 1e-12);
 
-        let (k3_x, k3_p) = (int.velocity(), self.panel_moment());
-        int.momentum += k3_p * delta;
-        int.transform += k3_x * delta;
+        state.panels.clear();
+        assert_eq!(state.aerodynamics().centre_of_pressure, None);
+    }
+
+    #[test]
+    fn static_margin() {
+        let mut state = rocket(Vec3::new(30., 0., 3.), Rotation::from_x(0.4));
+        let angles = [0., 0.05, 0.1, -0.1];
+
+        let points = state.stability(angles, 0.1);
+        assert_eq!(points.len(), 4);
+        assert_eq!(points[0].centre_of_pressure, None);
+        for (point, angle) in points.iter().zip(angles).skip(1) {
+            assert_eq!(point.angle_of_attack, angle);
+            assert_relative_eq!(point.centre_of_pressure.unwrap(), -0.5, epsilon = 1e-12);
+            assert_relative_eq!(point.static_margin.unwrap(), 5., epsilon = 1e-10);
+        }
 
-        let (k4_x, k4_p) = (int.velocity(), self.panel_moment());
+        // Canards ahead of the centre of mass move the centre of pressure forward
+        state
+            .panels
+            .push(Panel::new(Vec3::new(0.6, 0., 0.), Vec3::Z, 0.005));
+        let point = state.stability([0.1], 0.1)[0];
+        assert_relative_eq!(
+            point.centre_of_pressure.unwrap(),
+            -0.2 / 1.5,
+            epsilon = 1e-12
+        );
+        assert_relative_eq!(point.static_margin.unwrap(), 2. / 1.5, epsilon = 1e-10);
 
-        self.momentum += (k1_p + k2_p * 2. + k3_p * 2. + k4_p) * (delta / 6);
-        self.transform += (k1_x + k2_x * 2. + k3_x * 2. + k4_x) * (delta / 6);
+        state.panels[3].offset.x = 1.5;
+        assert!(state.stability([0.1], 0.1)[0].static_margin.unwrap() < 0.);
+    }
+
+    #[test]
+    fn off_axis_panels() {
+        // A swept fin above the body axis feels an axial force that pitches the rocket but
+        // doesn't move the centre of pressure along it
+        let mut state = rocket(Vec3::X, Rotation::ZERO);
+        state.panels = vec![Panel::new(
+            Vec3::new(-0.5, 0., 0.2),
+            Vec3::new(1., 0., 1.).normalize(),
+            0.01,
+        )];
+
+        let point = state.stability([0.1], 0.1)[0];
+        assert_relative_eq!(point.centre_of_pressure.unwrap(), -0.5, epsilon = 1e-12);
+        assert_relative_eq!(point.static_margin.unwrap(), 5., epsilon = 1e-10);
+    }
+
+    #[test]
+    fn invalid_caliber() {
+        let state = rocket(Vec3::X, Rotation::ZERO);
+
+        for caliber in [0., -0.1, f64::NAN, f64::INFINITY] {
+            let point = state.stability([0.1], caliber)[0];
+            assert_relative_eq!(point.centre_of_pressure.unwrap(), -0.5, epsilon = 1e-12);
+            assert_eq!(point.static_margin, None);
+        }
     }
 }
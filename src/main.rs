@@ -0,0 +1,297 @@
+//! Runs a scenario file and records the trajectory of every body
+
+use std::{
+    env,
+    error::Error,
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+    process::ExitCode,
+};
+
+use simscript_physics::{
+    integrator::Integrator,
+    recording::{self, Recorder, RecordingFormat},
+    replay,
+    scenario::{EndCondition, Scenario, ScenarioError, Simulation},
+};
+
+const USAGE: &str = "\
+Usage: simscript-physics <SCENARIO> [OPTIONS]
+
+Runs a scenario file and writes the trajectory of every body
+
+Options:
+  -i, --integrator <NAME>  forward_euler (euler) or runge_kutta_4 (rk4)
+  -s, --step <SECONDS>     Step size, overriding the scenario
+  -e, --end <SECONDS>      End time, overriding the scenario
+  -d, --every <STEPS>      Only write every nth step, the last one is always written [default: 1]
+  -f, --format <FORMAT>    csv, jsonl or bin [default: from the output extension, csv otherwise]
+  -o, --output <PATH>      File to write to [default: standard output]
+  -v, --verify <PATH>      Check that the run reproduces a recording bit for bit instead of writing
+  -h, --help               Print this help
+";
+
+fn main() -> ExitCode {
+    let options = match Options::parse(env::args().skip(1)) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            print!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            eprintln!("error: {e}\n\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match run(&options) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}: {e}", options.scenario.display());
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Options {
+    scenario: PathBuf,
+    integrator: Option<Integrator>,
+    step: Option<f64>,
+    end: Option<f64>,
+    every: u64,
+    format: RecordingFormat,
+    output: Option<PathBuf>,
+    verify: Option<PathBuf>,
+}
+
+impl Options {
+    /// Parses the command line arguments, returning `None` if help was requested
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<Option<Self>, String> {
+        let mut args = args.into_iter();
+        let mut scenario = None;
+        let mut integrator = None;
+        let mut step = None;
+        let mut end = None;
+        let mut every = 1;
+        let mut format = None;
+        let mut output = None;
+        let mut verify = None;
+
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or(format!("`{arg}` expects a value"));
+
+            match arg.as_str() {
+                "-h" | "--help" => return Ok(None),
+                "-i" | "--integrator" => {
+                    integrator = Some(value()?.parse().map_err(|e| format!("{e}"))?);
+                }
+                "-s" | "--step" => {
+                    step =
+                        Some(seconds(&value()?).filter(|s| *s > 0.).ok_or(
+                            "step must be a finite, positive number of seconds".to_owned(),
+                        )?);
+                }
+                "-e" | "--end" => {
+                    end = Some(seconds(&value()?).ok_or(
+                        "end must be a finite, non negative number of seconds".to_owned(),
+                    )?);
+                }
+                "-d" | "--every" => {
+                    every = value()?
+                        .parse()
+                        .ok()
+                        .filter(|n| *n > 0)
+                        .ok_or("every must be a positive whole number".to_owned())?;
+                }
+                "-f" | "--format" => {
+                    format = Some(value()?.parse().map_err(|e| format!("{e}"))?);
+                }
+                "-o" | "--output" => output = Some(PathBuf::from(value()?)),
+                "-v" | "--verify" => verify = Some(PathBuf::from(value()?)),
+                _ if arg.starts_with('-') => return Err(format!("unknown option `{arg}`")),
+                _ if scenario.is_none() => scenario = Some(PathBuf::from(arg)),
+                _ => return Err(format!("unexpected argument `{arg}`")),
+            }
+        }
+
+        let format = format
+            .or_else(|| output.as_deref().and_then(RecordingFormat::from_extension))
+            .unwrap_or(RecordingFormat::Csv);
+
+        Ok(Some(Self {
+            scenario: scenario.ok_or("missing scenario file".to_owned())?,
+            integrator,
+            step,
+            end,
+            every,
+            format,
+            output,
+            verify,
+        }))
+    }
+}
+
+/// Parses a finite, non negative number of seconds
+fn seconds(s: &str) -> Option<f64> {
+    s.parse().ok().filter(|s: &f64| s.is_finite() && *s >= 0.)
+}
+
+fn run(options: &Options) -> Result<(), Box<dyn Error>> {
+    let source = fs::read_to_string(&options.scenario).map_err(ScenarioError::from)?;
+    let mut scenario = Scenario::from_toml(&source)?;
+
+    if let Some(integrator) = options.integrator {
+        scenario.integrator = integrator;
+    }
+    if let Some(step) = options.step {
+        scenario.step = step;
+    }
+    if let Some(end) = options.end {
+        scenario.end = EndCondition::Time(end);
+    }
+
+    let mut sim = scenario.build().map_err(|e| e.locate(&source))?;
+
+    if let Some(path) = &options.verify {
+        let recording = recording::read_file(path)?;
+        let replay = replay::verify(&mut sim, &recording)?;
+        eprintln!(
+            "reproduced {} samples over {} steps",
+            replay.samples, replay.steps
+        );
+        return Ok(());
+    }
+
+    let out: Box<dyn Write> = match &options.output {
+        Some(path) => Box::new(File::create(path)?),
+        None => Box::new(io::stdout().lock()),
+    };
+    let mut recorder = Recorder::new(BufWriter::new(out), options.format)?;
+
+    simulate(&mut sim, options.every, &mut recorder)?;
+    recorder.finish()?;
+    Ok(())
+}
+
+/// Runs the simulation to the end, recording the first, last and every `every`th step
+fn simulate(
+    sim: &mut Simulation,
+    every: u64,
+    recorder: &mut Recorder<impl Write>,
+) -> io::Result<()> {
+    recorder.sample_simulation(sim)?;
+    while sim.step() {
+        if sim.steps.is_multiple_of(every) || sim.is_finished() {
+            recorder.sample_simulation(sim)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use simscript_physics::scenario;
+
+    fn parse(args: &[&str]) -> Result<Option<Options>, String> {
+        Options::parse(args.iter().map(|s| s.to_string()))
+    }
+
+    #[test]
+    fn options() {
+        let options = parse(&[
+            "rocket.toml",
+            "-i",
+            "euler",
+            "--step",
+            "0.01",
+            "--end",
+            "2",
+            "-d",
+            "10",
+            "-o",
+            "out.jsonl",
+        ])
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(
+            options,
+            Options {
+                scenario: "rocket.toml".into(),
+                integrator: Some(Integrator::ForwardEuler),
+                step: Some(0.01),
+                end: Some(2.),
+                every: 10,
+                format: RecordingFormat::JsonLines,
+                output: Some("out.jsonl".into()),
+                verify: None,
+            }
+        );
+        assert_eq!(parse(&["a.toml", "--help"]), Ok(None));
+        assert_eq!(
+            parse(&["a.toml"]).unwrap().unwrap().format,
+            RecordingFormat::Csv
+        );
+    }
+
+    #[test]
+    fn invalid_options() {
+        assert!(parse(&[]).is_err());
+        assert!(parse(&["a.toml", "b.toml"]).is_err());
+        assert!(parse(&["a.toml", "--step", "0"]).is_err());
+        assert!(parse(&["a.toml", "--end", "-1"]).is_err());
+        assert!(parse(&["a.toml", "--every", "0"]).is_err());
+        assert!(parse(&["a.toml", "--format", "xml"]).is_err());
+        assert!(parse(&["a.toml", "--integrator"]).is_err());
+        assert!(parse(&["a.toml", "--verbose"]).is_err());
+    }
+
+    #[test]
+    fn records_steps() {
+        let mut sim = scenario::load_str(
+            r#"
+            step = 0.1
+            end = { steps = 7 }
+
+            [[bodies]]
+            name = "a"
+            mass = 1.0
+            inertia = { diagonal = [1.0, 1.0, 1.0] }
+
+            [[bodies]]
+            name = "b"
+            mass = 1.0
+            inertia = { diagonal = [1.0, 1.0, 1.0] }
+            velocity = [0.5, 0.0, 0.0]
+            "#,
+        )
+        .unwrap();
+
+        let mut recorder = Recorder::new(Vec::new(), RecordingFormat::Csv).unwrap();
+        simulate(&mut sim, 3, &mut recorder).unwrap();
+
+        let bytes = recorder.finish().unwrap();
+        let samples = recording::read(bytes.as_slice(), RecordingFormat::Csv).unwrap();
+        let rows: Vec<(u32, &str)> = samples
+            .iter()
+            .map(|s| ((s.time * 10.).round() as u32, s.body.as_str()))
+            .collect();
+        assert_eq!(
+            rows,
+            [
+                (0, "a"),
+                (0, "b"),
+                (3, "a"),
+                (3, "b"),
+                (6, "a"),
+                (6, "b"),
+                (7, "a"),
+                (7, "b")
+            ]
+        );
+    }
+}
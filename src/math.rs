@@ -0,0 +1,86 @@
+//! Floating point functions that `core` lacks.
+//!
+//! Uses the inherent [f64] methods with the `std` feature and `libm` without it, the same way
+//! glam picks its math backend. The `deterministic` feature always uses `libm`, whose results
+//! don't depend on the platform's math library.
+
+#[cfg(all(feature = "std", not(feature = "deterministic")))]
+mod backend {
+    #[inline]
+    pub(crate) fn sqrt(x: f64) -> f64 {
+        x.sqrt()
+    }
+
+    #[inline]
+    pub(crate) fn sin(x: f64) -> f64 {
+        x.sin()
+    }
+
+    #[inline]
+    pub(crate) fn cos(x: f64) -> f64 {
+        x.cos()
+    }
+
+    #[inline]
+    pub(crate) fn atan2(y: f64, x: f64) -> f64 {
+        y.atan2(x)
+    }
+
+    #[inline]
+    pub(crate) fn rem_euclid(x: f64, rhs: f64) -> f64 {
+        x.rem_euclid(rhs)
+    }
+}
+
+#[cfg(any(not(feature = "std"), feature = "deterministic"))]
+mod backend {
+    #[inline]
+    pub(crate) fn sqrt(x: f64) -> f64 {
+        libm::sqrt(x)
+    }
+
+    #[inline]
+    pub(crate) fn sin(x: f64) -> f64 {
+        libm::sin(x)
+    }
+
+    #[inline]
+    pub(crate) fn cos(x: f64) -> f64 {
+        libm::cos(x)
+    }
+
+    #[inline]
+    pub(crate) fn atan2(y: f64, x: f64) -> f64 {
+        libm::atan2(y, x)
+    }
+
+    #[inline]
+    pub(crate) fn rem_euclid(x: f64, rhs: f64) -> f64 {
+        let r = libm::fmod(x, rhs);
+        if r < 0. {
+            r + rhs.abs()
+        } else {
+            r
+        }
+    }
+}
+
+pub(crate) use backend::*;
+
+#[cfg(test)]
+mod backends {
+    use super::*;
+    use approx::assert_ulps_eq;
+
+    #[test]
+    fn matches_std() {
+        for x in [-7.5, -1., -0.25, 0., 0.3, 2., 13.] {
+            assert_ulps_eq!(sin(x), f64::sin(x));
+            assert_ulps_eq!(cos(x), f64::cos(x));
+            assert_ulps_eq!(atan2(x, 0.5), f64::atan2(x, 0.5));
+            assert_ulps_eq!(rem_euclid(x, 2.), f64::rem_euclid(x, 2.));
+            assert_ulps_eq!(rem_euclid(x, -2.), f64::rem_euclid(x, -2.));
+            assert_ulps_eq!(sqrt(x.abs()), f64::sqrt(x.abs()));
+        }
+    }
+}
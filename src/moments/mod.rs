@@ -6,10 +6,11 @@ use {
     approx_derive::Approx,
 };
 
+use crate::math;
 use crate::momentum::Momentum;
+use crate::overload::overload;
+use core::{iter::Sum, time::Duration};
 use glam::DVec3 as Vec3;
-use overload::overload;
-use std::{iter::Sum, ops, time::Duration};
 
 pub use force::Force;
 pub use torque::Torque;
@@ -89,7 +90,7 @@ impl Moment {
     #[inline]
     #[must_use]
     pub fn magnitude(&self) -> f64 {
-        (self.force.0.length_squared() * self.torque.0.length_squared()).sqrt()
+        math::sqrt(self.force.0.length_squared() * self.torque.0.length_squared())
     }
 }
 
@@ -112,7 +113,6 @@ impl Moment {
 /// Conversion implementations to create [Moment] from individual components.
 impl From<Force> for Moment {
     #[inline]
-    #[must_use]
     fn from(value: Force) -> Self {
         Self::from_force(value)
     }
@@ -120,7 +120,6 @@ impl From<Force> for Moment {
 
 impl From<Torque> for Moment {
     #[inline]
-    #[must_use]
     fn from(value: Torque) -> Self {
         Self::from_torque(value)
     }
@@ -129,7 +128,6 @@ impl From<Torque> for Moment {
 /// Implements summation over an iterator of [Moment] values.
 impl Sum for Moment {
     #[inline]
-    #[must_use]
     fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
         iter.fold(Self::ZERO, |a, b| a + b)
     }
@@ -7,10 +7,10 @@ use {
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::{inertia_mass::InertiaMass, velocity::Velocity};
+use crate::overload::overload;
+use crate::{inertia_mass::InertiaMass, moments::Moment, velocity::Velocity};
+use core::{iter::Sum, time::Duration};
 use glam::DVec3 as Vec3;
-use overload::overload;
-use std::{iter::Sum, ops};
 
 pub use angular_momentum::AngMom;
 pub use linear_momentum::LinMom;
@@ -82,10 +82,27 @@ impl Momentum {
     }
 }
 
+impl Momentum {
+    /// Divides the momentum by a time duration in seconds, returning the average [Moment].
+    #[inline]
+    #[must_use]
+    pub fn div_secs(&self, rhs: f64) -> Moment {
+        Moment::new(self.linear.div_secs(rhs), self.angular.div_secs(rhs))
+    }
+
+    /// Divides the momentum by a [Duration], returning the average [Moment].
+    ///
+    /// Note: this function uses [Momentum::div_secs] internally.
+    #[inline]
+    #[must_use]
+    pub fn div_dur(&self, rhs: &Duration) -> Moment {
+        self.div_secs(rhs.as_secs_f64())
+    }
+}
+
 /// Conversion implementations to create [Momentum] from individual components.
 impl From<LinMom> for Momentum {
     #[inline]
-    #[must_use]
     fn from(value: LinMom) -> Self {
         Self::from_linear(value)
     }
@@ -93,7 +110,6 @@ impl From<LinMom> for Momentum {
 
 impl From<AngMom> for Momentum {
     #[inline]
-    #[must_use]
     fn from(value: AngMom) -> Self {
         Self::from_angular(value)
     }
@@ -102,7 +118,6 @@ impl From<AngMom> for Momentum {
 /// Implements summation over an iterator of [Momentum] values.
 impl Sum for Momentum {
     #[inline]
-    #[must_use]
     fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
         iter.fold(Self::ZERO, |a, b| a + b)
     }
@@ -118,6 +133,10 @@ overload!((a: ?Momentum) / (b: f64) -> Momentum { Momentum::new(a.linear / b, a.
 overload!((a: &mut Momentum) *= (b: f64) { a.linear *= b; a.angular *= b; });
 overload!((a: &mut Momentum) /= (b: f64) { a.linear /= b; a.angular /= b; });
 
+overload!((a: ?Momentum) / (b: Duration) -> Moment { a.div_dur(&b) });
+overload!((a: ?Momentum) / (b: &Duration) -> Moment { a.div_dur(b) });
+
 overload!((a: ?Momentum) / (b: ?InertiaMass) -> Velocity { Velocity::new(a.linear / b.mass, a.angular / b.inv_inertia) });
+overload!((a: ?InertiaMass) * (b: ?Velocity) -> Momentum { Momentum::new(a.mass * b.linear, a.inertia * b.angular) });
 
 overload!(-(a: ?Momentum) -> Momentum { Momentum { linear: -a.linear, angular: -a.angular } });
@@ -0,0 +1,100 @@
+//! Operator overloading shorthand used by every quantity type.
+//!
+//! Follows the syntax of the `overload` crate, which cannot be used on `no_std` targets:
+//!
+//! - `overload!((a: ?A) + (b: ?B) -> C { ... })` implements `A + B` for owned and borrowed
+//!   operands. Drop the `?` to only implement the owned form of that operand.
+//! - `overload!((a: &mut A) += (b: ?B) { ... })` implements a compound assignment.
+//! - `overload!(-(a: ?A) -> A { ... })` implements negation.
+//!
+//! The generated impls refer to `core::ops` directly, callers don't need to import anything.
+
+macro_rules! overload {
+    (-($a:ident : ?$t:ty) -> $out:ty $body:block) => {
+        $crate::overload::overload!(@neg $a, $t, $out, $body);
+        $crate::overload::overload!(@neg $a, &$t, $out, $body);
+    };
+    (-($a:ident : $t:ty) -> $out:ty $body:block) => {
+        $crate::overload::overload!(@neg $a, $t, $out, $body);
+    };
+
+    (($a:ident : &mut $lt:ty) $op:tt ($b:ident : ?$rt:ty) $body:block) => {
+        $crate::overload::overload!(@assign $op, $a, $lt, $b, $rt, $body);
+        $crate::overload::overload!(@assign $op, $a, $lt, $b, &$rt, $body);
+    };
+    (($a:ident : &mut $lt:ty) $op:tt ($b:ident : $rt:ty) $body:block) => {
+        $crate::overload::overload!(@assign $op, $a, $lt, $b, $rt, $body);
+    };
+
+    (($a:ident : ?$lt:ty) $op:tt ($b:ident : ?$rt:ty) -> $out:ty $body:block) => {
+        $crate::overload::overload!(@binary $op, $a, $lt, $b, $rt, $out, $body);
+        $crate::overload::overload!(@binary $op, $a, $lt, $b, &$rt, $out, $body);
+        $crate::overload::overload!(@binary $op, $a, &$lt, $b, $rt, $out, $body);
+        $crate::overload::overload!(@binary $op, $a, &$lt, $b, &$rt, $out, $body);
+    };
+    (($a:ident : ?$lt:ty) $op:tt ($b:ident : $rt:ty) -> $out:ty $body:block) => {
+        $crate::overload::overload!(@binary $op, $a, $lt, $b, $rt, $out, $body);
+        $crate::overload::overload!(@binary $op, $a, &$lt, $b, $rt, $out, $body);
+    };
+    (($a:ident : $lt:ty) $op:tt ($b:ident : ?$rt:ty) -> $out:ty $body:block) => {
+        $crate::overload::overload!(@binary $op, $a, $lt, $b, $rt, $out, $body);
+        $crate::overload::overload!(@binary $op, $a, $lt, $b, &$rt, $out, $body);
+    };
+    (($a:ident : $lt:ty) $op:tt ($b:ident : $rt:ty) -> $out:ty $body:block) => {
+        $crate::overload::overload!(@binary $op, $a, $lt, $b, $rt, $out, $body);
+    };
+
+    (@binary +, $($rest:tt)*) => { $crate::overload::overload!(@binary_impl Add, add, $($rest)*); };
+    (@binary -, $($rest:tt)*) => { $crate::overload::overload!(@binary_impl Sub, sub, $($rest)*); };
+    (@binary *, $($rest:tt)*) => { $crate::overload::overload!(@binary_impl Mul, mul, $($rest)*); };
+    (@binary /, $($rest:tt)*) => { $crate::overload::overload!(@binary_impl Div, div, $($rest)*); };
+
+    (@assign +=, $($rest:tt)*) => {
+        $crate::overload::overload!(@assign_impl AddAssign, add_assign, $($rest)*);
+    };
+    (@assign -=, $($rest:tt)*) => {
+        $crate::overload::overload!(@assign_impl SubAssign, sub_assign, $($rest)*);
+    };
+    (@assign *=, $($rest:tt)*) => {
+        $crate::overload::overload!(@assign_impl MulAssign, mul_assign, $($rest)*);
+    };
+    (@assign /=, $($rest:tt)*) => {
+        $crate::overload::overload!(@assign_impl DivAssign, div_assign, $($rest)*);
+    };
+
+    (@binary_impl $trait:ident, $fn:ident, $a:ident, $lt:ty, $b:ident, $rt:ty, $out:ty, $body:block) => {
+        impl core::ops::$trait<$rt> for $lt {
+            type Output = $out;
+
+            #[inline]
+            fn $fn(self, rhs: $rt) -> Self::Output {
+                let $a = self;
+                let $b = rhs;
+                $body
+            }
+        }
+    };
+    (@assign_impl $trait:ident, $fn:ident, $a:ident, $lt:ty, $b:ident, $rt:ty, $body:block) => {
+        impl core::ops::$trait<$rt> for $lt {
+            #[inline]
+            fn $fn(&mut self, rhs: $rt) {
+                let $a = self;
+                let $b = rhs;
+                $body
+            }
+        }
+    };
+    (@neg $a:ident, $t:ty, $out:ty, $body:block) => {
+        impl core::ops::Neg for $t {
+            type Output = $out;
+
+            #[inline]
+            fn neg(self) -> Self::Output {
+                let $a = self;
+                $body
+            }
+        }
+    };
+}
+
+pub(crate) use overload;
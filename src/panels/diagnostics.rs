@@ -0,0 +1,90 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::DENSITY;
+use crate::frame::{Body, Point};
+use crate::math;
+use crate::moments::{Force, Moment};
+use crate::velocity::LinVel;
+use alloc::vec::Vec;
+use glam::{DQuat as Quat, DVec3 as Vec3};
+
+/// What a single panel contributes to the aerodynamics of an entity, expressed in the world
+/// frame
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PanelDiagnostics {
+    /// Velocity of the panel through the air, including the rotation of the entity
+    pub tip_velocity: LinVel,
+    /// Area of the panel facing the flow, negative when the flow hits its back
+    pub effective_area: f64,
+    pub force: Force,
+    /// Force and torque about the centre of mass
+    pub moment: Moment,
+}
+
+/// Aerodynamic state of an entity, see [State::aerodynamics](crate::State::aerodynamics)
+///
+/// Angles follow the aircraft convention in the body frame: `x` points forward, the angle of
+/// attack is measured in the `xz` plane and the sideslip towards `y`.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct AeroDiagnostics {
+    /// Contribution of each panel, in the same order as [State::panels](crate::State::panels)
+    pub panels: Vec<PanelDiagnostics>,
+    /// Sum of the panel moments, equal to [State::panel_moment](crate::State::panel_moment)
+    pub moment: Moment,
+    /// `ρ|v|²/2` of the centre of mass, in pascals
+    pub dynamic_pressure: f64,
+    /// Angle between the body `x` axis and the velocity in the `xz` plane, in radians
+    pub angle_of_attack: f64,
+    /// Angle between the velocity and the body `xz` plane, in radians
+    pub sideslip: f64,
+    /// Point of the line of action of the aerodynamic force closest to the centre of mass,
+    /// `None` without any force
+    pub centre_of_pressure: Option<Point<Body>>,
+}
+
+impl AeroDiagnostics {
+    /// Derives the aggregated quantities from the world frame velocity of the centre of mass and
+    /// panel moment of an entity with the given rotation
+    pub(crate) fn new(
+        panels: Vec<PanelDiagnostics>,
+        moment: Moment,
+        velocity: LinVel,
+        rotation: &Quat,
+    ) -> Self {
+        let to_body = rotation.inverse();
+        let v = to_body * velocity.0;
+        let force = moment.force.0;
+        let centre_of_pressure = (force != Vec3::ZERO).then(|| {
+            let offset = force.cross(moment.torque.0) / force.length_squared();
+            Point::new(to_body * offset)
+        });
+
+        Self {
+            panels,
+            moment,
+            dynamic_pressure: DENSITY * v.length_squared() / 2.,
+            angle_of_attack: math::atan2(v.z, v.x),
+            sideslip: math::atan2(v.y, math::sqrt(v.x * v.x + v.z * v.z)),
+            centre_of_pressure,
+        }
+    }
+}
+
+/// Static stability of an entity at one angle of attack, see
+/// [State::stability](crate::State::stability)
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StabilityPoint {
+    /// Angle of attack in the body `xz` plane, in radians
+    pub angle_of_attack: f64,
+    /// Position of the centre of pressure along the body `x` axis, relative to the centre of
+    /// mass, `None` if the panels produce no normal force at this angle
+    pub centre_of_pressure: Option<f64>,
+    /// Distance from the centre of mass back to the centre of pressure in calibers, positive
+    /// when the entity is statically stable, `None` without a centre of pressure or a valid
+    /// caliber
+    pub static_margin: Option<f64>,
+}
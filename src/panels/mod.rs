@@ -5,6 +5,10 @@ use crate::moments::{Force, Moment};
 use crate::velocity::{AngVel, LinVel, Velocity};
 use glam::{DQuat as Quat, DVec3 as Vec3};
 
+pub use diagnostics::{AeroDiagnostics, PanelDiagnostics, StabilityPoint};
+
+mod diagnostics;
+
 /// Represents a simulated "aerodynamic" panel.
 ///
 /// Used to heavily approximate the effects of aerodynamics on a simulated entity
@@ -34,9 +38,14 @@ impl Panel {
         }
     }
 
+    /// Calculates the area facing a relative velocity, negative if it hits the back of the panel.
+    pub fn effective_area(&self, rel_vel: &LinVel) -> f64 {
+        self.normal.dot(rel_vel.0.normalize_or_zero()) * self.area
+    }
+
     /// Calculates aerodynamic force based on relative velocity.
     pub fn to_force(&self, rel_vel: &LinVel) -> Force {
-        let area = self.normal.dot(rel_vel.0.normalize_or_zero()) * self.area;
+        let area = self.effective_area(rel_vel);
         Force::from_vec3(DENSITY * rel_vel.0.length_squared() * HALF_C_D * area * -self.normal)
     }
 
@@ -67,6 +76,20 @@ impl Panel {
         let force = rotated.to_force(&vel);
         Moment::from_force_and_offset(force, rotated.offset)
     }
+
+    /// Breaks down [Panel::to_moment] into the intermediate quantities it is computed from
+    pub fn diagnose(&self, vel: &Velocity, rot: &Quat) -> PanelDiagnostics {
+        let rotated = self.rotated(rot);
+        let tip_velocity = rotated.tip_velocity(vel);
+        let force = rotated.to_force(&tip_velocity);
+
+        PanelDiagnostics {
+            tip_velocity,
+            effective_area: rotated.effective_area(&tip_velocity),
+            force,
+            moment: Moment::from_force_and_offset(force, rotated.offset),
+        }
+    }
 }
 
 #[cfg(test)]
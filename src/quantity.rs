@@ -0,0 +1,348 @@
+//! Shared foundation for the strongly typed physical quantities.
+//!
+//! Every quantity is a newtype around either a [Vec3](glam::DVec3) or an [f64]. The macros in
+//! this module generate the boilerplate common to all of them (constants, constructors,
+//! conversions and same-type arithmetic), while the relation table at the bottom of the file lists
+//! every product and quotient between *different* quantities. Anything not in the table does not
+//! compile, so dimensionally invalid expressions are rejected by the type checker:
+//!
+//! ```compile_fail
+//! use simscript_physics::{moments::{Force, Torque}};
+//! let _ = Force::X + Torque::X;
+//! ```
+//!
+//! ```compile_fail
+//! use simscript_physics::{inertia_mass::Mass, moments::Force};
+//! let _ = Force::X * Mass::new(2.);
+//! ```
+//!
+//! ```compile_fail
+//! use simscript_physics::{inertia_mass::Inertia, velocity::LinVel};
+//! let _ = Inertia::cylinder_x(1., 1., 1.) * LinVel::X;
+//! ```
+//!
+//! Dimensions in the table are given as SI base unit exponents `[kg m s]`.
+
+use crate::overload::overload;
+use crate::{
+    acceleration::{AngAcc, LinAcc},
+    energy::{Energy, Power},
+    inertia_mass::{Inertia, Mass},
+    moments::{Force, Torque},
+    momentum::{AngMom, LinMom},
+    transform::Translation,
+    velocity::{AngVel, LinVel},
+};
+use core::time::Duration;
+
+/// Defines a [Vec3](glam::DVec3) backed quantity with the standard constants, constructors and
+/// arithmetic.
+///
+/// The second argument is the lower case name of the quantity used in the generated docs, an
+/// optional third one selects the scalar type of the vector and defaults to [f64]. Like
+/// the other macros in this module it expects `Vec3` (and `Duration` for [time_product]) to be in
+/// scope at the call site, as they are in every quantity module.
+macro_rules! vector_quantity {
+    ($(#[$attr:meta])* pub struct $name:ident; $noun:literal) => {
+        $crate::quantity::vector_quantity! { $(#[$attr])* pub struct $name; $noun; f64 }
+    };
+    ($(#[$attr:meta])* pub struct $name:ident; $noun:literal; $scalar:ident) => {
+        $(#[$attr])*
+        #[cfg_attr(feature = "approx", derive(approx_derive::Approx))]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        #[derive(Debug, Default, Clone, Copy, PartialEq)]
+        pub struct $name(pub Vec3);
+
+        impl $name {
+            #[doc = concat!("A zero ", $noun, " vector.")]
+            pub const ZERO: Self = Self::splat(0.);
+
+            #[doc = concat!("[", stringify!($name), "] of magnitude one in all directions.")]
+            pub const ONE: Self = Self::splat(1.);
+
+            #[doc = concat!("Unit ", $noun, " in the positive X direction.")]
+            pub const X: Self = Self::with_x(1.);
+            #[doc = concat!("Unit ", $noun, " in the positive Y direction.")]
+            pub const Y: Self = Self::with_y(1.);
+            #[doc = concat!("Unit ", $noun, " in the positive Z direction.")]
+            pub const Z: Self = Self::with_z(1.);
+
+            #[doc = concat!("Unit ", $noun, " in the negative X direction.")]
+            pub const NEG_X: Self = Self::with_x(-1.);
+            #[doc = concat!("Unit ", $noun, " in the negative Y direction.")]
+            pub const NEG_Y: Self = Self::with_y(-1.);
+            #[doc = concat!("Unit ", $noun, " in the negative Z direction.")]
+            pub const NEG_Z: Self = Self::with_z(-1.);
+
+            #[doc = concat!(
+                "Creates a new [", stringify!($name),
+                "] with the specified `x`, `y`, and `z` components."
+            )]
+            #[inline]
+            #[must_use]
+            pub const fn new(x: $scalar, y: $scalar, z: $scalar) -> Self {
+                Self(Vec3::new(x, y, z))
+            }
+
+            #[doc = concat!("Creates a [", stringify!($name), "] from an existing [Vec3].")]
+            #[inline]
+            #[must_use]
+            pub const fn from_vec3(v: Vec3) -> Self {
+                Self(v)
+            }
+
+            #[doc = concat!("Creates a [", stringify!($name), "] where all components are set to `v`.")]
+            #[inline]
+            #[must_use]
+            pub const fn splat(v: $scalar) -> Self {
+                Self::new(v, v, v)
+            }
+
+            #[doc = concat!("Creates a [", stringify!($name), "] with only the X component set.")]
+            #[inline]
+            #[must_use]
+            pub const fn with_x(x: $scalar) -> Self {
+                Self::new(x, 0., 0.)
+            }
+
+            #[doc = concat!("Creates a [", stringify!($name), "] with only the Y component set.")]
+            #[inline]
+            #[must_use]
+            pub const fn with_y(y: $scalar) -> Self {
+                Self::new(0., y, 0.)
+            }
+
+            #[doc = concat!("Creates a [", stringify!($name), "] with only the Z component set.")]
+            #[inline]
+            #[must_use]
+            pub const fn with_z(z: $scalar) -> Self {
+                Self::new(0., 0., z)
+            }
+        }
+
+        $crate::quantity::vector_ops!($name, $scalar);
+    };
+}
+
+/// Implements conversions, [Sum](core::iter::Sum) and same-type arithmetic for a
+/// [Vec3](glam::DVec3) backed quantity that has a `ZERO` constant.
+macro_rules! vector_ops {
+    ($name:ident) => {
+        $crate::quantity::vector_ops!($name, f64);
+    };
+    ($name:ident, $scalar:ident) => {
+        impl From<Vec3> for $name {
+            #[inline]
+            fn from(value: Vec3) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<$name> for Vec3 {
+            #[inline]
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        $crate::quantity::common_ops!($name, $scalar);
+    };
+}
+
+/// Implements conversions, [Sum](core::iter::Sum) and same-type arithmetic for an [f64] backed
+/// quantity that has a `ZERO` constant.
+macro_rules! scalar_ops {
+    ($name:ident, $scalar:ident) => {
+        impl From<$scalar> for $name {
+            #[inline]
+            fn from(value: $scalar) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<$name> for $scalar {
+            #[inline]
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        $crate::quantity::common_ops!($name, $scalar);
+    };
+}
+
+/// Arithmetic shared by [vector_ops] and [scalar_ops].
+macro_rules! common_ops {
+    ($name:ident, $scalar:ident) => {
+        impl core::iter::Sum for $name {
+            #[inline]
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold(Self::ZERO, |a, b| a + b)
+            }
+        }
+
+        $crate::overload::overload!((a: ?$name) + (b: ?$name) -> $name { $name(a.0 + b.0) });
+        $crate::overload::overload!((a: ?$name) - (b: ?$name) -> $name { $name(a.0 - b.0) });
+        $crate::overload::overload!((a: &mut $name) += (b: ?$name) { a.0 += b.0 });
+        $crate::overload::overload!((a: &mut $name) -= (b: ?$name) { a.0 -= b.0 });
+
+        $crate::overload::overload!((a: ?$name) * (b: $scalar) -> $name { $name(a.0 * b) });
+        $crate::overload::overload!((a: ?$name) / (b: $scalar) -> $name { $name(a.0 / b) });
+        $crate::overload::overload!((a: &mut $name) *= (b: $scalar) { a.0 *= b });
+        $crate::overload::overload!((a: &mut $name) /= (b: $scalar) { a.0 /= b });
+
+        $crate::overload::overload!(-(a: ?$name) -> $name { $name(-a.0) });
+    };
+}
+
+/// `$rate * Duration = $product`, along with the inverse `$product / Duration = $rate`.
+///
+/// Generates `mul_secs`/`mul_dur` on the rate and `div_secs`/`div_dur` on the product.
+macro_rules! time_product {
+    ($rate:ident * Duration = $product:ident) => {
+        time_product!($rate * Duration = $product; f64, as_secs_f64);
+    };
+    ($rate:ident * Duration = $product:ident; $scalar:ident, $as_secs:ident) => {
+        impl $rate {
+            #[doc = concat!(
+                "Scales by a time duration in seconds, returning a [", stringify!($product), "]."
+            )]
+            #[inline]
+            #[must_use]
+            pub fn mul_secs(&self, rhs: $scalar) -> $product {
+                $product(self.0 * rhs)
+            }
+
+            #[doc = concat!("Scales by a [Duration], returning a [", stringify!($product), "].")]
+            ///
+            /// Note: this function uses `mul_secs` internally, if performance is of the essence,
+            /// it might be a good idea to use it directly to avoid unnecessary [Duration]
+            /// conversions
+            #[inline]
+            #[must_use]
+            pub fn mul_dur(&self, rhs: &Duration) -> $product {
+                self.mul_secs(rhs.$as_secs())
+            }
+        }
+
+        impl $product {
+            #[doc = concat!(
+                "Divides by a time duration in seconds, returning the average [",
+                stringify!($rate), "]."
+            )]
+            #[inline]
+            #[must_use]
+            pub fn div_secs(&self, rhs: $scalar) -> $rate {
+                $rate(self.0 / rhs)
+            }
+
+            #[doc = concat!(
+                "Divides by a [Duration], returning the average [", stringify!($rate), "]."
+            )]
+            ///
+            /// Note: this function uses `div_secs` internally.
+            #[inline]
+            #[must_use]
+            pub fn div_dur(&self, rhs: &Duration) -> $rate {
+                self.div_secs(rhs.$as_secs())
+            }
+        }
+
+        overload!((a: ?$rate) * (b: Duration) -> $product { a.mul_dur(&b) });
+        overload!((a: ?$rate) * (b: &Duration) -> $product { a.mul_dur(b) });
+        overload!((a: ?$product) / (b: Duration) -> $rate { a.div_dur(&b) });
+        overload!((a: ?$product) / (b: &Duration) -> $rate { a.div_dur(b) });
+    };
+}
+
+/// `$scalar * $vector = $product` in both operand orders, and `$product / $scalar = $vector`.
+macro_rules! scalar_product {
+    ($scalar:ident * $vector:ident = $product:ident) => {
+        overload!((a: ?$scalar) * (b: ?$vector) -> $product { $product(b.0 * a.0) });
+        overload!((a: ?$vector) * (b: ?$scalar) -> $product { $product(a.0 * b.0) });
+        overload!((a: ?$product) / (b: ?$scalar) -> $vector { $vector(a.0 / b.0) });
+    };
+}
+
+/// `$tensor * $vector = $product`, and `$product / $tensor = $vector` where the divisor is the
+/// already inverted tensor, as cached in [InertiaMass::inv_inertia](crate::inertia_mass::InertiaMass).
+macro_rules! tensor_product {
+    ($tensor:ident * $vector:ident = $product:ident) => {
+        overload!((a: ?$tensor) * (b: ?$vector) -> $product { $product(a.0.mul_vec3(b.0)) });
+        overload!((a: ?$product) / (b: ?$tensor) -> $vector { $vector(b.0.mul_vec3(a.0)) });
+    };
+}
+
+pub(crate) use {common_ops, scalar_ops, vector_ops, vector_quantity};
+#[cfg(feature = "f32")]
+pub(crate) use {scalar_product, tensor_product, time_product};
+
+// Relation table. Rotation and the composite quantities (`Velocity`, `Momentum`, ...) are not
+// plain newtypes and implement their relations next to their definitions.
+
+time_product!(LinVel * Duration = Translation); // [0 1 -1] * [0 0 1] = [0 1 0]
+time_product!(LinAcc * Duration = LinVel); // [0 1 -2] * [0 0 1] = [0 1 -1]
+time_product!(AngAcc * Duration = AngVel); // [0 0 -2] * [0 0 1] = [0 0 -1]
+time_product!(Force * Duration = LinMom); // [1 1 -2] * [0 0 1] = [1 1 -1]
+time_product!(Torque * Duration = AngMom); // [1 2 -2] * [0 0 1] = [1 2 -1]
+time_product!(Power * Duration = Energy); // [1 2 -3] * [0 0 1] = [1 2 -2]
+
+scalar_product!(Mass * LinVel = LinMom); // [1 0 0] * [0 1 -1] = [1 1 -1]
+scalar_product!(Mass * LinAcc = Force); // [1 0 0] * [0 1 -2] = [1 1 -2]
+
+tensor_product!(Inertia * AngVel = AngMom); // [1 2 0] * [0 0 -1] = [1 2 -1]
+tensor_product!(Inertia * AngAcc = Torque); // [1 2 0] * [0 0 -2] = [1 2 -2]
+
+#[cfg(test)]
+mod relations {
+    use super::*;
+    use approx::assert_ulps_eq;
+    use glam::{DMat3 as Mat3, DVec3 as Vec3};
+
+    #[test]
+    fn time() {
+        let dur = Duration::from_millis(500);
+
+        assert_ulps_eq!(LinVel::X * dur, Translation::new(0.5, 0., 0.));
+        assert_ulps_eq!(Translation::X / dur, LinVel::with_x(2.));
+        assert_ulps_eq!(LinVel::Y / dur, LinAcc::with_y(2.));
+        assert_ulps_eq!(AngVel::Z / dur, AngAcc::with_z(2.));
+        assert_ulps_eq!(LinMom::X / dur, Force::with_x(2.));
+        assert_ulps_eq!(AngMom::X / dur, Torque::with_x(2.));
+        assert_ulps_eq!(Energy::new(3.) / dur, Power::new(6.));
+    }
+
+    #[test]
+    fn scalar() {
+        let m = Mass::new(4.);
+        let v = LinVel::new(1., 2., 3.);
+
+        assert_ulps_eq!(m * v, LinMom::new(4., 8., 12.));
+        assert_ulps_eq!(v * m, m * v);
+        assert_ulps_eq!((m * v) / m, v);
+        assert_ulps_eq!(m * LinAcc::Z, Force::with_z(4.));
+        assert_ulps_eq!(Force::X / m, LinAcc::with_x(0.25));
+    }
+
+    #[test]
+    fn tensor() {
+        let inertia = Inertia::new(Mat3::from_diagonal(Vec3::new(1., 2., 4.)));
+        let inv_inertia = Inertia::new(inertia.0.inverse());
+        let w = AngVel::new(1., 1., 1.);
+
+        assert_ulps_eq!(inertia * w, AngMom::new(1., 2., 4.));
+        assert_ulps_eq!((inertia * w) / inv_inertia, w);
+        assert_ulps_eq!(inertia * AngAcc::Y, Torque::with_y(2.));
+        assert_ulps_eq!(Torque::Z / inv_inertia, AngAcc::with_z(0.25));
+    }
+
+    #[test]
+    fn macro_generated_constants() {
+        assert_ulps_eq!(Force::ONE, Force::X + Force::Y + Force::Z);
+        assert_ulps_eq!(-Torque::NEG_Y, Torque::Y);
+        assert_ulps_eq!(
+            [LinMom::X, LinMom::Y].into_iter().sum::<LinMom>(),
+            LinMom::new(1., 1., 0.)
+        );
+    }
+}
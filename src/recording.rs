@@ -0,0 +1,842 @@
+//! Recording trajectories and reading them back
+//!
+//! A [Recorder] writes [Sample]s of one or more states to CSV, JSON Lines or a compact binary
+//! format, either at a fixed interval of simulated time or whenever it's told to. [read] loads a
+//! recording back into [Sample]s, whatever its format.
+//!
+//! All three formats store floating point numbers exactly, reading a recording gives back the
+//! same values that were recorded. Infinities and NaN are kept as well, JSON Lines writes them as
+//! strings such as `"inf"` since JSON has no such numbers. Only the binary format keeps the sign
+//! and payload of a NaN.
+
+use std::{
+    error::Error,
+    fmt,
+    fs::File,
+    io::{self, BufRead, BufReader, Read, Write},
+    path::Path,
+    str::FromStr,
+    time::Duration,
+};
+
+use glam::{DQuat as Quat, DVec3 as Vec3};
+use serde::{Deserialize, Serialize};
+
+use crate::moments::{Force, Moment, Torque};
+use crate::momentum::{AngMom, LinMom, Momentum};
+use crate::transform::{Rotation, Transform, Translation};
+use crate::velocity::{AngVel, LinVel, Velocity};
+use crate::State;
+
+/// Header row of CSV recordings
+///
+/// Every vector is in the world frame. `panel_forces` holds the force on each panel as
+/// space separated `x y z` triplets.
+pub const CSV_HEADER: &str = "time,body,x,y,z,qx,qy,qz,qw,vx,vy,vz,wx,wy,wz,\
+px,py,pz,lx,ly,lz,fx,fy,fz,tx,ty,tz,panel_forces";
+
+/// First bytes of a binary recording, followed by a little endian `u16` version
+pub const BINARY_MAGIC: [u8; 4] = *b"SSPR";
+
+/// Version of the binary format written by [Recorder]
+pub const BINARY_VERSION: u16 = 1;
+
+/// Number of values in a sample besides the time, body and panel forces
+pub(crate) const VALUES: usize = 25;
+
+/// The state of a single body at one point in time
+///
+/// Serialized with non-finite numbers as strings, see the [module](self) docs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(into = "JsonSample", from = "JsonSample")]
+pub struct Sample {
+    /// Simulated time in seconds
+    pub time: f64,
+    /// Name of the body
+    pub body: String,
+    /// Position and rotation of the body in the world frame
+    pub transform: Transform,
+    /// Velocity of the body, expressed in the world frame
+    pub velocity: Velocity,
+    /// Momentum of the body, expressed in the world frame
+    pub momentum: Momentum,
+    /// Total moment applied by the panels, expressed in the world frame
+    pub moment: Moment,
+    /// Force on each panel in the world frame, in the same order as [State::panels]
+    pub panel_forces: Vec<Force>,
+}
+
+impl Sample {
+    /// Samples a state
+    pub fn new(time: Duration, body: &str, state: &State) -> Self {
+        let velocity = state.velocity();
+        let rot = state.transform.rotation.0;
+
+        Self {
+            time: time.as_secs_f64(),
+            body: body.to_owned(),
+            transform: state.transform,
+            velocity,
+            momentum: state.momentum,
+            moment: state.panel_moment(),
+            panel_forces: state
+                .panels
+                .iter()
+                .map(|panel| panel.to_moment(&velocity, &rot).force)
+                .collect(),
+        }
+    }
+
+    /// Flattens everything but the time, body and panel forces, in [CSV_HEADER] order
+    pub(crate) fn values(&self) -> [f64; VALUES] {
+        let Transform {
+            translation: Translation(p),
+            rotation: Rotation(q),
+        } = self.transform;
+        let [v, w] = [self.velocity.linear.0, self.velocity.angular.0];
+        let [l, a] = [self.momentum.linear.0, self.momentum.angular.0];
+        let [f, t] = [self.moment.force.0, self.moment.torque.0];
+
+        [
+            p.x, p.y, p.z, q.x, q.y, q.z, q.w, v.x, v.y, v.z, w.x, w.y, w.z, l.x, l.y, l.z, a.x,
+            a.y, a.z, f.x, f.y, f.z, t.x, t.y, t.z,
+        ]
+    }
+
+    fn from_values(time: f64, body: String, v: &[f64; VALUES], panel_forces: Vec<Force>) -> Self {
+        let vec3 = |i: usize| Vec3::new(v[i], v[i + 1], v[i + 2]);
+
+        Self {
+            time,
+            body,
+            transform: Transform::new(
+                Translation(vec3(0)),
+                Rotation(Quat::from_xyzw(v[3], v[4], v[5], v[6])),
+            ),
+            velocity: Velocity::new(LinVel(vec3(7)), AngVel(vec3(10))),
+            momentum: Momentum::new(LinMom(vec3(13)), AngMom(vec3(16))),
+            moment: Moment::new(Force(vec3(19)), Torque(vec3(22))),
+            panel_forces,
+        }
+    }
+}
+
+/// A number of a [JsonSample], written as a string when it isn't finite
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(transparent)]
+struct JsonFloat(#[serde(with = "crate::non_finite")] f64);
+
+/// The serialized form of a [Sample], laid out like its fields
+#[derive(Serialize, Deserialize)]
+struct JsonSample {
+    time: JsonFloat,
+    body: String,
+    transform: JsonTransform,
+    velocity: JsonPair,
+    momentum: JsonPair,
+    moment: JsonMoment,
+    panel_forces: Vec<[JsonFloat; 3]>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct JsonTransform {
+    translation: [JsonFloat; 3],
+    rotation: [JsonFloat; 4],
+}
+
+/// A [Velocity] or a [Momentum]
+#[derive(Serialize, Deserialize)]
+struct JsonPair {
+    linear: [JsonFloat; 3],
+    angular: [JsonFloat; 3],
+}
+
+#[derive(Serialize, Deserialize)]
+struct JsonMoment {
+    force: [JsonFloat; 3],
+    torque: [JsonFloat; 3],
+}
+
+impl From<Sample> for JsonSample {
+    fn from(sample: Sample) -> Self {
+        let v = sample.values().map(JsonFloat);
+        let vec3 = |i: usize| [v[i], v[i + 1], v[i + 2]];
+
+        Self {
+            time: JsonFloat(sample.time),
+            body: sample.body,
+            transform: JsonTransform {
+                translation: vec3(0),
+                rotation: [v[3], v[4], v[5], v[6]],
+            },
+            velocity: JsonPair {
+                linear: vec3(7),
+                angular: vec3(10),
+            },
+            momentum: JsonPair {
+                linear: vec3(13),
+                angular: vec3(16),
+            },
+            moment: JsonMoment {
+                force: vec3(19),
+                torque: vec3(22),
+            },
+            panel_forces: sample
+                .panel_forces
+                .iter()
+                .map(|force| force.0.to_array().map(JsonFloat))
+                .collect(),
+        }
+    }
+}
+
+impl From<JsonSample> for Sample {
+    fn from(json: JsonSample) -> Self {
+        let floats = [
+            json.transform.translation.as_slice(),
+            &json.transform.rotation,
+            &json.velocity.linear,
+            &json.velocity.angular,
+            &json.momentum.linear,
+            &json.momentum.angular,
+            &json.moment.force,
+            &json.moment.torque,
+        ]
+        .concat();
+        let mut values = [0.; VALUES];
+        for (value, float) in values.iter_mut().zip(floats) {
+            *value = float.0;
+        }
+
+        Self::from_values(
+            json.time.0,
+            json.body,
+            &values,
+            json.panel_forces
+                .into_iter()
+                .map(|[x, y, z]| Force::new(x.0, y.0, z.0))
+                .collect(),
+        )
+    }
+}
+
+/// File format of a recording
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RecordingFormat {
+    /// Comma separated values with a [CSV_HEADER] row
+    Csv,
+    /// One JSON object per line
+    JsonLines,
+    /// Little endian values following [BINARY_MAGIC] and [BINARY_VERSION]
+    Binary,
+}
+
+impl RecordingFormat {
+    /// Guesses the format from a `.csv`, `.jsonl` or `.bin` extension
+    pub fn from_extension(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "csv" => Some(Self::Csv),
+            "jsonl" => Some(Self::JsonLines),
+            "bin" => Some(Self::Binary),
+            _ => None,
+        }
+    }
+}
+
+/// Returned when parsing the name of an unknown [RecordingFormat]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseFormatError;
+
+impl fmt::Display for ParseFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown format, expected `csv`, `jsonl` or `bin`")
+    }
+}
+
+impl Error for ParseFormatError {}
+
+impl FromStr for RecordingFormat {
+    type Err = ParseFormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(Self::Csv),
+            "jsonl" | "json_lines" => Ok(Self::JsonLines),
+            "bin" | "binary" => Ok(Self::Binary),
+            _ => Err(ParseFormatError),
+        }
+    }
+}
+
+/// Writes [Sample]s of states as they are simulated
+#[derive(Debug)]
+pub struct Recorder<W: Write> {
+    writer: W,
+    format: RecordingFormat,
+    interval: Option<Duration>,
+    next: Duration,
+}
+
+impl<W: Write> Recorder<W> {
+    /// Creates a recorder and writes the header of the format
+    ///
+    /// Without an interval every call to [Recorder::sample] records, which suits event driven
+    /// sampling.
+    ///
+    /// # Errors
+    /// Returns any error from writing the header
+    pub fn new(mut writer: W, format: RecordingFormat) -> io::Result<Self> {
+        match format {
+            RecordingFormat::Csv => writeln!(writer, "{CSV_HEADER}")?,
+            RecordingFormat::JsonLines => {}
+            RecordingFormat::Binary => {
+                writer.write_all(&BINARY_MAGIC)?;
+                writer.write_all(&BINARY_VERSION.to_le_bytes())?;
+            }
+        }
+
+        Ok(Self {
+            writer,
+            format,
+            interval: None,
+            next: Duration::ZERO,
+        })
+    }
+
+    /// Only records when [Recorder::sample] reaches the next multiple of `interval`
+    ///
+    /// A zero interval records every call
+    #[must_use]
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = Some(interval).filter(|i| !i.is_zero());
+        self
+    }
+
+    /// Returns `true` if [Recorder::sample] would record at `time`
+    #[must_use]
+    pub fn is_due(&self, time: Duration) -> bool {
+        time >= self.next
+    }
+
+    /// Records every body if a sample is due at `time`, returning whether it did
+    ///
+    /// # Errors
+    /// Returns any error from writing the samples
+    pub fn sample<'a>(
+        &mut self,
+        time: Duration,
+        bodies: impl IntoIterator<Item = (&'a str, &'a State)>,
+    ) -> io::Result<bool> {
+        if !self.is_due(time) {
+            return Ok(false);
+        }
+
+        for (body, state) in bodies {
+            self.record(&Sample::new(time, body, state))?;
+        }
+
+        if let Some(interval) = self.interval {
+            let into = time.as_nanos() % interval.as_nanos();
+            self.next = time + interval - Duration::from_nanos(into as u64);
+        }
+
+        Ok(true)
+    }
+
+    /// Records every body of a [Simulation](crate::scenario::Simulation) if a sample is due
+    ///
+    /// # Errors
+    /// Returns any error from writing the samples
+    #[cfg(feature = "scenario")]
+    pub fn sample_simulation(&mut self, sim: &crate::scenario::Simulation) -> io::Result<bool> {
+        let bodies = sim.names.iter().map(String::as_str).zip(&sim.states);
+        self.sample(sim.time, bodies)
+    }
+
+    /// Writes a sample regardless of the interval
+    ///
+    /// # Errors
+    /// Returns any error from writing the sample
+    pub fn record(&mut self, sample: &Sample) -> io::Result<()> {
+        match self.format {
+            RecordingFormat::Csv => write_csv(&mut self.writer, sample),
+            RecordingFormat::JsonLines => {
+                serde_json::to_writer(&mut self.writer, sample)?;
+                writeln!(self.writer)
+            }
+            RecordingFormat::Binary => write_binary(&mut self.writer, sample),
+        }
+    }
+
+    /// Flushes and returns the writer
+    ///
+    /// # Errors
+    /// Returns any error from flushing
+    pub fn finish(mut self) -> io::Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+fn write_csv(out: &mut impl Write, sample: &Sample) -> io::Result<()> {
+    write!(out, "{},{}", sample.time, csv_field(&sample.body))?;
+    for value in sample.values() {
+        write!(out, ",{value}")?;
+    }
+
+    write!(out, ",")?;
+    for (i, force) in sample.panel_forces.iter().enumerate() {
+        let separator = if i == 0 { "" } else { " " };
+        write!(out, "{separator}{} {} {}", force.0.x, force.0.y, force.0.z)?;
+    }
+    writeln!(out)
+}
+
+/// Quotes a CSV field if it contains a separator, a quote or a line break
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_owned()
+    }
+}
+
+fn write_binary(out: &mut impl Write, sample: &Sample) -> io::Result<()> {
+    let len = |n: usize| {
+        u32::try_from(n).map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "too long"))
+    };
+
+    out.write_all(&sample.time.to_le_bytes())?;
+    out.write_all(&len(sample.body.len())?.to_le_bytes())?;
+    out.write_all(sample.body.as_bytes())?;
+    for value in sample.values() {
+        out.write_all(&value.to_le_bytes())?;
+    }
+
+    out.write_all(&len(sample.panel_forces.len())?.to_le_bytes())?;
+    for force in &sample.panel_forces {
+        for value in force.0.to_array() {
+            out.write_all(&value.to_le_bytes())?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Reasons a recording can't be read
+#[derive(Debug)]
+pub enum ReadError {
+    /// The recording couldn't be read
+    Io(io::Error),
+    /// The format couldn't be guessed from the file extension
+    UnknownFormat,
+    /// The record at the given position, starting at one, is invalid
+    ///
+    /// For the text formats this is the line number
+    Malformed {
+        /// Position of the record
+        record: usize,
+        /// What is wrong with it
+        message: String,
+    },
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "couldn't read recording: {e}"),
+            Self::UnknownFormat => write!(f, "unknown recording format"),
+            Self::Malformed { record, message } => write!(f, "record {record}: {message}"),
+        }
+    }
+}
+
+impl Error for ReadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ReadError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+fn malformed(record: usize, message: impl fmt::Display) -> ReadError {
+    ReadError::Malformed {
+        record,
+        message: message.to_string(),
+    }
+}
+
+/// Reads a recording file, guessing the format from its extension
+///
+/// # Errors
+/// Returns [ReadError::UnknownFormat] for unknown extensions, see [read] for the rest
+pub fn read_file(path: impl AsRef<Path>) -> Result<Vec<Sample>, ReadError> {
+    let format = RecordingFormat::from_extension(path.as_ref()).ok_or(ReadError::UnknownFormat)?;
+    read(BufReader::new(File::open(path)?), format)
+}
+
+/// Reads every sample of a recording
+///
+/// # Errors
+/// Returns the first invalid record found
+pub fn read(reader: impl BufRead, format: RecordingFormat) -> Result<Vec<Sample>, ReadError> {
+    match format {
+        RecordingFormat::Csv => read_csv(reader),
+        RecordingFormat::JsonLines => read_json_lines(reader),
+        RecordingFormat::Binary => read_binary(reader),
+    }
+}
+
+fn read_csv(mut reader: impl BufRead) -> Result<Vec<Sample>, ReadError> {
+    let mut header = String::new();
+    reader.read_line(&mut header)?;
+    if strip_line_break(&header) != CSV_HEADER {
+        return Err(malformed(1, "missing header"));
+    }
+
+    let mut samples = Vec::new();
+    let mut lines = 1;
+    loop {
+        let record = lines + 1;
+        let mut line = String::new();
+        // A quoted field can hold line breaks, so read until every quote is closed
+        while reader.read_line(&mut line)? != 0 {
+            lines += 1;
+            if line.matches('"').count().is_multiple_of(2) {
+                break;
+            }
+        }
+        if line.is_empty() {
+            break;
+        }
+
+        let line = strip_line_break(&line);
+        if line.is_empty() {
+            continue;
+        }
+
+        let fields = split_csv(line).ok_or_else(|| malformed(record, "unterminated quote"))?;
+        if fields.len() != VALUES + 3 {
+            return Err(malformed(record, format!("expected {} fields", VALUES + 3)));
+        }
+
+        let number = |s: &str| s.parse::<f64>().map_err(|e| malformed(record, e));
+        let mut values = [0.; VALUES];
+        for (value, field) in values.iter_mut().zip(&fields[2..]) {
+            *value = number(field)?;
+        }
+
+        let forces = fields[VALUES + 2]
+            .split_whitespace()
+            .map(number)
+            .collect::<Result<Vec<_>, _>>()?;
+        if forces.len() % 3 != 0 {
+            return Err(malformed(record, "panel forces must be x y z triplets"));
+        }
+
+        samples.push(Sample::from_values(
+            number(&fields[0])?,
+            fields[1].clone(),
+            &values,
+            forces
+                .chunks_exact(3)
+                .map(|f| Force::new(f[0], f[1], f[2]))
+                .collect(),
+        ));
+    }
+
+    Ok(samples)
+}
+
+/// Removes the `\n` or `\r\n` ending a line
+fn strip_line_break(line: &str) -> &str {
+    match line.strip_suffix('\n') {
+        Some(line) => line.strip_suffix('\r').unwrap_or(line),
+        None => line,
+    }
+}
+
+/// Splits a CSV line into fields, returning `None` if a quote isn't closed
+fn split_csv(line: &str) -> Option<Vec<String>> {
+    let mut fields = vec![String::new()];
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        let field = fields.last_mut()?;
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(String::new()),
+            c => field.push(c),
+        }
+    }
+
+    (!quoted).then_some(fields)
+}
+
+fn read_json_lines(reader: impl BufRead) -> Result<Vec<Sample>, ReadError> {
+    let mut samples = Vec::new();
+
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        samples.push(serde_json::from_str(&line).map_err(|e| malformed(i + 1, e))?);
+    }
+
+    Ok(samples)
+}
+
+fn read_binary(mut reader: impl Read) -> Result<Vec<Sample>, ReadError> {
+    let mut header = [0; 6];
+    reader.read_exact(&mut header)?;
+    if header[..4] != BINARY_MAGIC {
+        return Err(malformed(1, "not a binary recording"));
+    }
+    let version = u16::from_le_bytes([header[4], header[5]]);
+    if version != BINARY_VERSION {
+        return Err(malformed(1, format!("unsupported version {version}")));
+    }
+
+    let mut samples = Vec::new();
+    loop {
+        let record = samples.len() + 1;
+        let mut time = Vec::with_capacity(8);
+        if reader.by_ref().take(8).read_to_end(&mut time)? == 0 {
+            break;
+        }
+        let time = <[u8; 8]>::try_from(time).map_err(|_| malformed(record, "truncated record"))?;
+
+        let truncated = |e: io::Error| match e.kind() {
+            io::ErrorKind::UnexpectedEof => malformed(record, "truncated record"),
+            _ => e.into(),
+        };
+        let read_u32 = |reader: &mut dyn Read| {
+            let mut bytes = [0; 4];
+            reader.read_exact(&mut bytes).map_err(truncated)?;
+            Ok::<_, ReadError>(u32::from_le_bytes(bytes) as usize)
+        };
+        let read_f64 = |reader: &mut dyn Read| {
+            let mut bytes = [0; 8];
+            reader.read_exact(&mut bytes).map_err(truncated)?;
+            Ok::<_, ReadError>(f64::from_le_bytes(bytes))
+        };
+
+        // Reads through `take` so a corrupt length can't allocate more than the recording holds
+        let len = read_u32(&mut reader)?;
+        let mut body = Vec::new();
+        reader.by_ref().take(len as u64).read_to_end(&mut body)?;
+        if body.len() != len {
+            return Err(malformed(record, "truncated record"));
+        }
+        let body = String::from_utf8(body).map_err(|e| malformed(record, e))?;
+
+        let mut values = [0.; VALUES];
+        for value in &mut values {
+            *value = read_f64(&mut reader)?;
+        }
+
+        let panels = read_u32(&mut reader)?;
+        let mut panel_forces = Vec::with_capacity(panels.min(1024));
+        for _ in 0..panels {
+            let x = read_f64(&mut reader)?;
+            let y = read_f64(&mut reader)?;
+            let z = read_f64(&mut reader)?;
+            panel_forces.push(Force::new(x, y, z));
+        }
+
+        samples.push(Sample::from_values(
+            f64::from_le_bytes(time),
+            body,
+            &values,
+            panel_forces,
+        ));
+    }
+
+    Ok(samples)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use rstest::rstest;
+
+    fn rocket() -> State {
+        fixtures::rocket()
+            .transform(Transform::new(
+                Translation::new(0., 0., 100.),
+                Rotation::from_y(0.3),
+            ))
+            .momentum(Momentum::from_vec3s(
+                Vec3::new(60., 0., 10.),
+                Vec3::new(0.01, 0.02, 0.),
+            ))
+            .build()
+    }
+
+    fn record(format: RecordingFormat) -> (Vec<u8>, Vec<Sample>) {
+        let mut state = rocket();
+        let mut recorder = Recorder::new(Vec::new(), format)
+            .unwrap()
+            .with_interval(Duration::from_millis(10));
+        let mut expected = Vec::new();
+
+        for step in 0..=25 {
+            let time = Duration::from_millis(step * 4);
+            if recorder.is_due(time) {
+                expected.push(Sample::new(time, "a \"rocket\", really", &state));
+            }
+            recorder
+                .sample(time, [("a \"rocket\", really", &state)])
+                .unwrap();
+            state.runge_kutta_4(Duration::from_millis(4));
+        }
+
+        (recorder.finish().unwrap(), expected)
+    }
+
+    #[rstest]
+    #[case(RecordingFormat::Csv)]
+    #[case(RecordingFormat::JsonLines)]
+    #[case(RecordingFormat::Binary)]
+    fn round_trip(#[case] format: RecordingFormat) {
+        let (bytes, expected) = record(format);
+        let samples = read(bytes.as_slice(), format).unwrap();
+
+        let times: Vec<f64> = samples.iter().map(|s| s.time).collect();
+        assert_eq!(
+            times,
+            [0., 0.012, 0.02, 0.032, 0.04, 0.052, 0.06, 0.072, 0.08, 0.092, 0.1]
+        );
+        assert_eq!(samples, expected);
+        assert_eq!(samples[3].panel_forces.len(), 2);
+    }
+
+    #[rstest]
+    #[case(RecordingFormat::Csv)]
+    #[case(RecordingFormat::JsonLines)]
+    #[case(RecordingFormat::Binary)]
+    fn non_finite(#[case] format: RecordingFormat) {
+        let mut sample = Sample::new(Duration::ZERO, "rocket", &rocket());
+        sample.transform.translation.0.x = f64::INFINITY;
+        sample.velocity.angular.0.y = f64::NEG_INFINITY;
+        sample.panel_forces[1].0.z = f64::NAN;
+
+        let mut recorder = Recorder::new(Vec::new(), format).unwrap();
+        recorder.record(&sample).unwrap();
+        let bytes = recorder.finish().unwrap();
+        let mut samples = read(bytes.as_slice(), format).unwrap();
+
+        assert!(samples[0].panel_forces[1].0.z.is_nan());
+        samples[0].panel_forces[1].0.z = 0.;
+        sample.panel_forces[1].0.z = 0.;
+        assert_eq!(samples, [sample]);
+    }
+
+    #[test]
+    fn csv_line_breaks() {
+        let state = rocket();
+        let mut recorder = Recorder::new(Vec::new(), RecordingFormat::Csv).unwrap();
+        recorder
+            .sample(Duration::ZERO, [("two\nlines", &state), ("cr\r\n", &state)])
+            .unwrap();
+
+        let bytes = recorder.finish().unwrap();
+        let samples = read(bytes.as_slice(), RecordingFormat::Csv).unwrap();
+        assert_eq!(samples[0].body, "two\nlines");
+        assert_eq!(samples[1].body, "cr\r\n");
+    }
+
+    #[test]
+    fn sample_contents() {
+        let state = rocket();
+        let sample = Sample::new(Duration::from_secs(1), "rocket", &state);
+
+        assert_eq!(sample.moment, state.panel_moment());
+        assert_eq!(
+            sample.panel_forces.iter().copied().sum::<Force>(),
+            state.panel_moment().force
+        );
+    }
+
+    #[test]
+    fn event_driven() {
+        let state = rocket();
+        let mut recorder = Recorder::new(Vec::new(), RecordingFormat::Csv).unwrap();
+
+        for _ in 0..3 {
+            assert!(recorder
+                .sample(Duration::ZERO, [("rocket", &state)])
+                .unwrap());
+        }
+
+        let bytes = recorder.finish().unwrap();
+        assert_eq!(
+            read(bytes.as_slice(), RecordingFormat::Csv).unwrap().len(),
+            3
+        );
+    }
+
+    #[test]
+    fn malformed() {
+        let (mut bytes, _) = record(RecordingFormat::Binary);
+        bytes.truncate(bytes.len() - 3);
+        assert!(matches!(
+            read(bytes.as_slice(), RecordingFormat::Binary),
+            Err(ReadError::Malformed { record: 11, .. })
+        ));
+
+        let (mut bytes, _) = record(RecordingFormat::Binary);
+        bytes.extend_from_slice(&[0; 5]);
+        assert!(matches!(
+            read(bytes.as_slice(), RecordingFormat::Binary),
+            Err(ReadError::Malformed { record: 12, .. })
+        ));
+
+        let mut bytes = [BINARY_MAGIC.as_slice(), &BINARY_VERSION.to_le_bytes()].concat();
+        bytes.extend_from_slice(&1f64.to_le_bytes());
+        bytes.extend_from_slice(&u32::MAX.to_le_bytes());
+        bytes.extend_from_slice(b"rocket");
+        assert!(matches!(
+            read(bytes.as_slice(), RecordingFormat::Binary),
+            Err(ReadError::Malformed { record: 1, .. })
+        ));
+
+        let (bytes, _) = record(RecordingFormat::Csv);
+        let text = String::from_utf8(bytes)
+            .unwrap()
+            .replace("0.012,", "0.012,\"");
+        assert!(matches!(
+            read(text.as_bytes(), RecordingFormat::Csv),
+            Err(ReadError::Malformed { record: 3, .. })
+        ));
+
+        assert!(matches!(
+            read("time,body\n".as_bytes(), RecordingFormat::Csv),
+            Err(ReadError::Malformed { record: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn formats() {
+        assert_eq!("bin".parse(), Ok(RecordingFormat::Binary));
+        assert_eq!("xml".parse::<RecordingFormat>(), Err(ParseFormatError));
+        assert_eq!(
+            RecordingFormat::from_extension(Path::new("out/run.jsonl")),
+            Some(RecordingFormat::JsonLines)
+        );
+    }
+}
@@ -0,0 +1,110 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::transform::Rotation;
+
+/// Configuration and diagnostics for keeping the rotation quaternion of a [State](crate::State)
+/// at unit length.
+///
+/// Every composition of rotations during integration introduces a tiny rounding error in the
+/// length of the quaternion. The stepping functions check the drift after every step and
+/// renormalize the rotation once it exceeds [Renormalization::tolerance].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Renormalization {
+    /// Largest allowed difference between the length of the quaternion and one.
+    ///
+    /// Set to [f64::INFINITY] to disable renormalization, or zero to renormalize every step.
+    ///
+    /// Serialized as a string when it isn't finite, as formats like JSON have no infinity.
+    #[cfg_attr(feature = "serde", serde(with = "crate::non_finite"))]
+    pub tolerance: f64,
+    /// Number of times the rotation has been renormalized.
+    pub count: u64,
+}
+
+impl Renormalization {
+    /// The default drift tolerance.
+    pub const DEFAULT_TOLERANCE: f64 = 1e-12;
+
+    /// Renormalization with the default tolerance.
+    pub const DEFAULT: Self = Self::new(Self::DEFAULT_TOLERANCE);
+
+    /// Renormalization turned off.
+    pub const DISABLED: Self = Self::new(f64::INFINITY);
+
+    /// Creates a new [Renormalization] with the given tolerance and a zeroed counter.
+    #[inline]
+    #[must_use]
+    pub const fn new(tolerance: f64) -> Self {
+        Self {
+            tolerance,
+            count: 0,
+        }
+    }
+
+    /// Renormalizes `rot` if its length has drifted further than the tolerance from one.
+    ///
+    /// With the `strict_asserts` feature enabled this also asserts that the rotation is within
+    /// the tolerance afterwards, catching rotations that are NaN or cannot be normalized.
+    ///
+    /// # Returns
+    /// `true` if the rotation was renormalized
+    pub fn apply(&mut self, rot: &mut Rotation) -> bool {
+        let drifted = (rot.0.length() - 1.).abs() > self.tolerance;
+        if drifted {
+            *rot = rot.normalize();
+            self.count += 1;
+        }
+
+        #[cfg(feature = "strict_asserts")]
+        assert!(
+            (rot.0.length() - 1.).abs() <= self.tolerance.max(f64::EPSILON * 2.),
+            "rotation {rot:?} is not within {} of unit length",
+            self.tolerance
+        );
+
+        drifted
+    }
+}
+
+impl Default for Renormalization {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+#[cfg(test)]
+mod apply {
+    use super::*;
+    use glam::DQuat as Quat;
+
+    #[test]
+    fn within_tolerance() {
+        let mut renorm = Renormalization::new(1e-6);
+        let mut rot = Rotation(Quat::from_xyzw(0., 0., 0., 1. + 1e-9));
+
+        assert!(!renorm.apply(&mut rot));
+        assert_eq!(renorm.count, 0);
+        assert_eq!(rot.0.w, 1. + 1e-9);
+    }
+
+    #[test]
+    fn drifted() {
+        let mut renorm = Renormalization::new(1e-6);
+        let mut rot = Rotation(Quat::from_xyzw(0., 0., 0.6, 0.9));
+
+        assert!(renorm.apply(&mut rot));
+        assert!(rot.0.is_normalized());
+        assert_eq!(renorm.count, 1);
+    }
+
+    #[test]
+    fn disabled() {
+        let mut renorm = Renormalization::DISABLED;
+        let mut rot = Rotation(Quat::from_xyzw(0., 0., 0.6, 0.9));
+
+        assert!(!renorm.apply(&mut rot));
+        assert_eq!(renorm.count, 0);
+    }
+}
@@ -0,0 +1,277 @@
+//! Checking that a simulation reproduces a stored recording exactly
+//!
+//! [verify] steps a [Simulation] alongside a recording made by a
+//! [Recorder](crate::recording::Recorder) and compares every recorded value bit for bit, stopping
+//! at the first one that differs.
+//!
+//! Results are only reproducible across machines with the `deterministic` feature, which makes the
+//! transcendental functions independent of the platform's math library. Rust never fuses
+//! multiplications and additions on its own and the engine uses no randomness, so nothing else
+//! varies between runs.
+
+use std::{error::Error, fmt};
+
+use crate::recording::{Sample, CSV_HEADER};
+use crate::scenario::Simulation;
+
+/// Where a simulation first stopped matching a recording
+#[derive(Debug, Clone, PartialEq)]
+pub struct Divergence {
+    /// Number of steps the simulation had taken
+    pub step: u64,
+    /// Name of the first differing value, as in [CSV_HEADER]
+    pub field: String,
+    pub expected: Sample,
+    pub actual: Sample,
+}
+
+/// Summary of a successful [verify]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Replay {
+    /// Number of steps taken to reach the last sample
+    pub steps: u64,
+    /// Number of samples compared
+    pub samples: usize,
+}
+
+/// Reasons a simulation doesn't reproduce a recording
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplayError {
+    /// A recorded value differs from the simulated one
+    Diverged(Box<Divergence>),
+    /// The sample at the given index, starting at one, is of a body the simulation doesn't have
+    UnknownBody { sample: usize, body: String },
+    /// The simulation never reached the time of the sample at the given index, starting at one
+    Unmatched { sample: usize, time: f64 },
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Diverged(d) => write!(
+                f,
+                "diverged at step {} (t = {}s): body `{}` differs in `{}`",
+                d.step, d.expected.time, d.expected.body, d.field,
+            ),
+            Self::UnknownBody { sample, body } => {
+                write!(f, "sample {sample} is of unknown body `{body}`")
+            }
+            Self::Unmatched { sample, time } => {
+                write!(
+                    f,
+                    "the simulation never reaches sample {sample} at t = {time}s"
+                )
+            }
+        }
+    }
+}
+
+impl Error for ReplayError {}
+
+/// Steps the simulation until it has reproduced every sample of the recording
+///
+/// Samples are matched to the simulation by their time and body name, so recordings taken at any
+/// interval can be verified as long as the simulation is set up the same way.
+///
+/// # Errors
+/// Returns the first [Divergence], or a sample the simulation can't reproduce
+pub fn verify(sim: &mut Simulation, recording: &[Sample]) -> Result<Replay, ReplayError> {
+    let mut next = 0;
+
+    loop {
+        let time = sim.time.as_secs_f64();
+
+        while let Some(expected) = recording.get(next) {
+            if expected.time.to_bits() != time.to_bits() {
+                break;
+            }
+
+            let body = sim
+                .names
+                .iter()
+                .position(|name| *name == expected.body)
+                .ok_or_else(|| ReplayError::UnknownBody {
+                    sample: next + 1,
+                    body: expected.body.clone(),
+                })?;
+
+            let actual = Sample::new(sim.time, &expected.body, &sim.states[body]);
+            if let Some(field) = first_difference(expected, &actual) {
+                return Err(ReplayError::Diverged(Box::new(Divergence {
+                    step: sim.steps,
+                    field,
+                    expected: expected.clone(),
+                    actual,
+                })));
+            }
+
+            next += 1;
+        }
+
+        let Some(expected) = recording.get(next) else {
+            return Ok(Replay {
+                steps: sim.steps,
+                samples: next,
+            });
+        };
+
+        if expected.time < time || !sim.step() {
+            return Err(ReplayError::Unmatched {
+                sample: next + 1,
+                time: expected.time,
+            });
+        }
+    }
+}
+
+/// Names the first value whose bits differ between the samples
+fn first_difference(expected: &Sample, actual: &Sample) -> Option<String> {
+    let columns = CSV_HEADER.split(',').skip(2);
+    let values = expected.values().into_iter().zip(actual.values());
+
+    for (column, (e, a)) in columns.zip(values) {
+        if e.to_bits() != a.to_bits() {
+            return Some(column.to_owned());
+        }
+    }
+
+    if expected.panel_forces.len() != actual.panel_forces.len() {
+        return Some("panel_forces".to_owned());
+    }
+
+    let forces = expected.panel_forces.iter().zip(&actual.panel_forces);
+    for (i, (e, a)) in forces.enumerate() {
+        for (axis, (e, a)) in ["x", "y", "z"]
+            .into_iter()
+            .zip(e.0.to_array().into_iter().zip(a.0.to_array()))
+        {
+            if e.to_bits() != a.to_bits() {
+                return Some(format!("panel_forces[{i}].{axis}"));
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use crate::integrator::Integrator;
+    use crate::recording::{self, Recorder, RecordingFormat};
+    use crate::scenario;
+    use crate::transform::Translation;
+    use std::time::Duration;
+
+    const SCENARIO: &str = concat!(
+        r#"
+step = 0.002
+end = { time = 0.05 }
+
+"#,
+        fixtures::rocket_toml!(),
+        r#"rotation = [0.0, 0.3, 0.0]
+velocity = [30.0, 0.0, 5.0]
+angular_velocity = [0.2, -0.1, 0.4]
+
+[[bodies]]
+name = "ball"
+mass = 1.0
+inertia = { diagonal = [0.1, 0.1, 0.1] }
+velocity = [5.0, 5.0, 0.0]
+panels = [{ offset = [0.0, 0.0, 0.0], normal = [1.0, 0.0, 0.0], area = 0.02 }]
+"#
+    );
+
+    /// Records the scenario every 5 ms through the binary format
+    fn recording() -> Vec<Sample> {
+        let mut sim = scenario::load_str(SCENARIO).unwrap();
+        let mut recorder = Recorder::new(Vec::new(), RecordingFormat::Binary)
+            .unwrap()
+            .with_interval(Duration::from_millis(5));
+
+        recorder.sample_simulation(&sim).unwrap();
+        while sim.step() {
+            recorder.sample_simulation(&sim).unwrap();
+        }
+
+        let bytes = recorder.finish().unwrap();
+        recording::read(bytes.as_slice(), RecordingFormat::Binary).unwrap()
+    }
+
+    #[test]
+    fn reproduces() {
+        let recording = recording();
+        let mut sim = scenario::load_str(SCENARIO).unwrap();
+
+        let replay = verify(&mut sim, &recording).unwrap();
+        assert_eq!(
+            replay,
+            Replay {
+                steps: 25,
+                samples: 22
+            }
+        );
+    }
+
+    #[test]
+    fn first_divergence() {
+        let mut recording = recording();
+        let mut sim = scenario::load_str(SCENARIO).unwrap();
+        sim.states[1].transform.translation += Translation::new(0., 0., 1e-12);
+
+        recording.truncate(4);
+        let Err(ReplayError::Diverged(d)) = verify(&mut sim, &recording) else {
+            panic!("expected a divergence");
+        };
+        assert_eq!(d.step, 0);
+        assert_eq!(d.expected.body, "ball");
+        assert_eq!(d.field, "z");
+
+        let mut sim = scenario::load_str(SCENARIO).unwrap();
+        sim.integrator = Integrator::ForwardEuler;
+        let error = verify(&mut sim, &recording).unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("diverged at step 3 (t = 0.006s): body `rocket` differs in `x`"));
+    }
+
+    #[test]
+    fn unmatched_samples() {
+        let mut recording = recording();
+        recording[2].body = "comet".to_owned();
+        let mut sim = scenario::load_str(SCENARIO).unwrap();
+        assert_eq!(
+            verify(&mut sim, &recording),
+            Err(ReplayError::UnknownBody {
+                sample: 3,
+                body: "comet".to_owned()
+            })
+        );
+
+        let mut recording = recording_with_time(0.0051);
+        let mut sim = scenario::load_str(SCENARIO).unwrap();
+        assert!(matches!(
+            verify(&mut sim, &recording),
+            Err(ReplayError::Unmatched { sample: 3, .. })
+        ));
+
+        recording.truncate(2);
+        recording.push(Sample {
+            time: 1.,
+            ..recording[0].clone()
+        });
+        let mut sim = scenario::load_str(SCENARIO).unwrap();
+        assert!(matches!(
+            verify(&mut sim, &recording),
+            Err(ReplayError::Unmatched { sample: 3, .. })
+        ));
+    }
+
+    fn recording_with_time(time: f64) -> Vec<Sample> {
+        let mut recording = recording();
+        recording[2].time = time;
+        recording
+    }
+}
@@ -0,0 +1,599 @@
+//! Describing complete simulations in TOML files
+//!
+//! A scenario lists the bodies to simulate along with the integrator, the step size and when to
+//! stop. [load] and [load_str] turn a file into a runnable [Simulation], reporting the line and
+//! column of anything that is wrong with it.
+//!
+//! ```toml
+//! integrator = "runge_kutta_4"
+//! step = 0.001
+//! end = { time = 10.0 }
+//!
+//! [[bodies]]
+//! name = "rocket"
+//! mass = 2.0
+//! inertia = { cylinder_x = { length = 1.2, radius = 0.05 } }
+//! position = [0.0, 0.0, 100.0]
+//! rotation = [0.0, 0.3, 0.0]
+//! velocity = [30.0, 0.0, 5.0]
+//! panels = [
+//!     { offset = [-0.5, 0.0, 0.0], normal = [0.0, 1.0, 0.0], area = 0.01 },
+//!     { offset = [-0.5, 0.0, 0.0], normal = [0.0, 0.0, 1.0], area = 0.01 },
+//! ]
+//! ```
+//!
+//! Vectors are `[x, y, z]` arrays in the world frame, except for the panels which are relative to
+//! the body. Rotations are rotation vectors, the axis scaled by the angle in radians.
+//!
+//! Scenarios cover the bodies, their panels, the integrator, the step size and the end condition.
+//! They have no force elements or environment settings (gravity, wind, air density): the engine
+//! has neither, panels are its only source of force and the air density is fixed.
+
+use std::{error::Error, fmt, fs, io, path::Path, time::Duration};
+
+use glam::{DMat3 as Mat3, DVec3 as Vec3};
+use serde::{de::IgnoredAny, Deserialize, Serialize};
+use toml::Spanned;
+
+use crate::inertia_mass::{Inertia, InertiaMass, Mass};
+use crate::integrator::Integrator;
+use crate::panels::Panel;
+use crate::renormalization::Renormalization;
+use crate::transform::{Rotation, Transform, Translation};
+use crate::velocity::Velocity;
+use crate::{BuildError, State, StateBuilder};
+
+/// A complete simulation, as written in a scenario file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Scenario {
+    #[serde(default)]
+    pub integrator: Integrator,
+    /// Step size in seconds
+    pub step: f64,
+    pub end: EndCondition,
+    pub bodies: Vec<BodySpec>,
+}
+
+/// When a [Simulation] stops
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EndCondition {
+    /// Stop once the given number of seconds has been simulated
+    ///
+    /// The last step is shortened to end exactly at this time
+    Time(f64),
+    /// Stop after the given number of steps
+    Steps(u64),
+}
+
+impl EndCondition {
+    /// Returns `false` for end times that aren't a finite, non negative number of seconds
+    fn is_valid(&self) -> bool {
+        match *self {
+            Self::Time(time) => Duration::try_from_secs_f64(time).is_ok(),
+            Self::Steps(_) => true,
+        }
+    }
+}
+
+/// Initial conditions and mass properties of a single body
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BodySpec {
+    #[serde(default)]
+    pub name: String,
+    /// Mass in kilograms
+    pub mass: f64,
+    pub inertia: InertiaSpec,
+    #[serde(default)]
+    pub position: Vec3,
+    /// Rotation vector, see [Rotation::from_rotation_vector]
+    #[serde(default)]
+    pub rotation: Vec3,
+    #[serde(default)]
+    pub velocity: Vec3,
+    #[serde(default)]
+    pub angular_velocity: Vec3,
+    #[serde(default)]
+    pub panels: Vec<Panel>,
+    /// See [Renormalization::tolerance]
+    #[serde(default = "default_tolerance")]
+    pub renormalization_tolerance: f64,
+}
+
+fn default_tolerance() -> f64 {
+    Renormalization::DEFAULT_TOLERANCE
+}
+
+/// How the inertia tensor of a [BodySpec] is given
+///
+/// The cylinder shapes use the mass of the body
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InertiaSpec {
+    /// Principal moments along the body axes
+    Diagonal(Vec3),
+    /// Full tensor, row by row
+    Matrix([[f64; 3]; 3]),
+    CylinderX {
+        length: f64,
+        radius: f64,
+    },
+    CylinderY {
+        length: f64,
+        radius: f64,
+    },
+    CylinderZ {
+        length: f64,
+        radius: f64,
+    },
+}
+
+impl InertiaSpec {
+    pub fn to_inertia(&self, mass: f64) -> Inertia {
+        match *self {
+            Self::Diagonal(moments) => Inertia::from_diagonal(moments),
+            Self::Matrix(rows) => Inertia::new(Mat3::from_cols_array_2d(&rows).transpose()),
+            Self::CylinderX { length, radius } => Inertia::cylinder_x(length, radius, mass),
+            Self::CylinderY { length, radius } => Inertia::cylinder_y(length, radius, mass),
+            Self::CylinderZ { length, radius } => Inertia::cylinder_z(length, radius, mass),
+        }
+    }
+}
+
+impl BodySpec {
+    /// Builds the initial [State] of the body
+    ///
+    /// # Errors
+    /// Returns the [BuildError] of the first invalid property
+    pub fn to_state(&self) -> Result<State, BuildError> {
+        let mass = InertiaMass::new(Mass::new(self.mass), self.inertia.to_inertia(self.mass));
+        let rotation = Rotation::from_rotation_vector(self.rotation);
+        let velocity = Velocity::from_vec3s(self.velocity, self.angular_velocity);
+
+        StateBuilder::new()
+            .mass(mass)
+            .transform(Transform::new(Translation(self.position), rotation))
+            .momentum(mass.rotated(rotation.0) * velocity)
+            .panels(self.panels.clone())
+            .renormalization(Renormalization::new(self.renormalization_tolerance))
+            .try_build()
+    }
+}
+
+impl Scenario {
+    /// Parses a scenario from TOML without validating it
+    ///
+    /// # Errors
+    /// Returns [ScenarioError::Parse] if the source isn't a well formed scenario
+    pub fn from_toml(source: &str) -> Result<Self, ScenarioError> {
+        toml::from_str(source).map_err(|e| ScenarioError::Parse {
+            location: e.span().map(|span| Location::of(source, span.start)),
+            message: e.message().to_owned(),
+        })
+    }
+
+    /// Validates the scenario and sets up the [Simulation]
+    ///
+    /// Errors returned from here carry no [Location], use [load_str] to get them.
+    ///
+    /// # Errors
+    /// Returns the first invalid setting or body found
+    pub fn build(&self) -> Result<Simulation, ScenarioError> {
+        let step = Duration::try_from_secs_f64(self.step)
+            .ok()
+            .filter(|step| !step.is_zero())
+            .ok_or(ScenarioError::InvalidStep { location: None })?;
+
+        if !self.end.is_valid() {
+            return Err(ScenarioError::InvalidEnd { location: None });
+        }
+
+        let states = self
+            .bodies
+            .iter()
+            .enumerate()
+            .map(|(index, body)| {
+                body.to_state().map_err(|error| ScenarioError::Body {
+                    index,
+                    location: None,
+                    error,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Simulation {
+            names: self.bodies.iter().map(|body| body.name.clone()).collect(),
+            states,
+            integrator: self.integrator,
+            step,
+            end: self.end,
+            time: Duration::ZERO,
+            steps: 0,
+        })
+    }
+}
+
+/// Reads a scenario file and sets up the [Simulation]
+///
+/// # Errors
+/// Returns [ScenarioError::Io] if the file can't be read, see [load_str] for the rest
+pub fn load(path: impl AsRef<Path>) -> Result<Simulation, ScenarioError> {
+    load_str(&fs::read_to_string(path)?)
+}
+
+/// Parses a scenario and sets up the [Simulation]
+///
+/// # Errors
+/// Returns the first problem found, along with its [Location] in the source
+pub fn load_str(source: &str) -> Result<Simulation, ScenarioError> {
+    Scenario::from_toml(source)?
+        .build()
+        .map_err(|e| e.locate(source))
+}
+
+/// A runnable simulation of every body in a [Scenario]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Simulation {
+    /// Names of the bodies, in the same order as [Simulation::states]
+    pub names: Vec<String>,
+    pub states: Vec<State>,
+    pub integrator: Integrator,
+    pub step: Duration,
+    pub end: EndCondition,
+    /// Simulated time so far
+    pub time: Duration,
+    /// Number of steps taken so far
+    pub steps: u64,
+}
+
+impl Simulation {
+    /// Checks the step size and end condition with the same rules as [Scenario::build]
+    ///
+    /// Useful for simulations that weren't built from a scenario, such as deserialized ones.
+    ///
+    /// # Errors
+    /// Returns [ScenarioError::InvalidStep] or [ScenarioError::InvalidEnd], without a location
+    pub fn validate(&self) -> Result<(), ScenarioError> {
+        if self.step.is_zero() {
+            return Err(ScenarioError::InvalidStep { location: None });
+        }
+        if !self.end.is_valid() {
+            return Err(ScenarioError::InvalidEnd { location: None });
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` once the [EndCondition] has been reached
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        match self.end {
+            EndCondition::Time(_) => self.remaining().is_zero(),
+            EndCondition::Steps(steps) => self.steps >= steps,
+        }
+    }
+
+    /// Steps every body forward, returning `false` without stepping if the simulation is finished
+    pub fn step(&mut self) -> bool {
+        if self.is_finished() {
+            return false;
+        }
+
+        let delta = self.step.min(self.remaining());
+        self.integrator.step_all(&mut self.states, delta);
+        self.time += delta;
+        self.steps += 1;
+        true
+    }
+
+    /// Steps until the [EndCondition] is reached
+    pub fn run(&mut self) {
+        while self.step() {}
+    }
+
+    /// Simulated time left before the end time, unbounded when ending after a number of steps
+    ///
+    /// End times [Scenario::build] would reject are treated as already reached if negative or NaN,
+    /// and as never reached if too large.
+    pub(crate) fn remaining(&self) -> Duration {
+        match self.end {
+            EndCondition::Time(time) => match Duration::try_from_secs_f64(time) {
+                Ok(end) => end.saturating_sub(self.time),
+                Err(_) if time > 0. => Duration::MAX,
+                Err(_) => Duration::ZERO,
+            },
+            EndCondition::Steps(_) => Duration::MAX,
+        }
+    }
+}
+
+/// A position in a scenario file, both starting at one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Location {
+    /// Finds the line and column of a byte offset into `source`
+    fn of(source: &str, offset: usize) -> Self {
+        let before = &source[..offset.min(source.len())];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+
+        Self {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        }
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+/// Reasons a scenario can't be loaded
+#[derive(Debug)]
+pub enum ScenarioError {
+    /// The file couldn't be read
+    Io(io::Error),
+    /// The source isn't valid TOML or doesn't match the scenario format
+    Parse {
+        location: Option<Location>,
+        message: String,
+    },
+    /// The step size is not a finite, positive number of seconds
+    InvalidStep { location: Option<Location> },
+    /// The end time is not a finite, non negative number of seconds
+    InvalidEnd { location: Option<Location> },
+    /// The body at the given index can't be turned into a valid [State]
+    Body {
+        index: usize,
+        location: Option<Location>,
+        error: BuildError,
+    },
+}
+
+impl ScenarioError {
+    /// Returns where in the source the problem is, if known
+    #[must_use]
+    pub fn location(&self) -> Option<Location> {
+        match self {
+            Self::Io(_) => None,
+            Self::Parse { location, .. }
+            | Self::InvalidStep { location }
+            | Self::InvalidEnd { location }
+            | Self::Body { location, .. } => *location,
+        }
+    }
+
+    /// Fills in the location of an error returned by [Scenario::build] by finding the offending
+    /// value in the `source` the scenario was parsed from
+    #[must_use]
+    pub fn locate(mut self, source: &str) -> Self {
+        let Ok(spans) = toml::from_str::<Spans>(source) else {
+            return self;
+        };
+        let at = |span: &Spanned<IgnoredAny>| Some(Location::of(source, span.span().start));
+
+        match &mut self {
+            Self::InvalidStep { location } => *location = spans.step.as_ref().and_then(at),
+            Self::InvalidEnd { location } => *location = spans.end.as_ref().and_then(at),
+            Self::Body {
+                index,
+                location,
+                error,
+            } => {
+                let Some(body) = spans.bodies.get(*index) else {
+                    return self;
+                };
+                let inner = body.get_ref();
+                let span = match *error {
+                    BuildError::InvalidMass => inner.mass.as_ref(),
+                    BuildError::InvalidInertia => inner.inertia.as_ref(),
+                    BuildError::ZeroPanelNormal(i)
+                    | BuildError::NonFinitePanelOffset(i)
+                    | BuildError::NegativePanelArea(i) => inner.panels.get(i),
+                    _ => None,
+                };
+                *location = span
+                    .and_then(at)
+                    .or_else(|| Some(Location::of(source, body.span().start)));
+            }
+            Self::Io(_) | Self::Parse { .. } => {}
+        }
+
+        self
+    }
+}
+
+/// Positions of the values validation errors can point at
+#[derive(Deserialize)]
+struct Spans {
+    step: Option<Spanned<IgnoredAny>>,
+    end: Option<Spanned<IgnoredAny>>,
+    #[serde(default)]
+    bodies: Vec<Spanned<BodySpans>>,
+}
+
+#[derive(Deserialize)]
+struct BodySpans {
+    mass: Option<Spanned<IgnoredAny>>,
+    inertia: Option<Spanned<IgnoredAny>>,
+    #[serde(default)]
+    panels: Vec<Spanned<IgnoredAny>>,
+}
+
+impl fmt::Display for ScenarioError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(location) = self.location() {
+            write!(f, "{location}: ")?;
+        }
+
+        match self {
+            Self::Io(e) => write!(f, "couldn't read scenario: {e}"),
+            Self::Parse { message, .. } => write!(f, "{message}"),
+            Self::InvalidStep { .. } => write!(f, "step must be a finite, positive number"),
+            Self::InvalidEnd { .. } => write!(f, "end time must be finite and not negative"),
+            Self::Body { index, error, .. } => write!(f, "body {index}: {error}"),
+        }
+    }
+}
+
+impl Error for ScenarioError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Body { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ScenarioError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use approx::assert_relative_eq;
+
+    const ROCKET: &str = concat!(
+        r#"
+integrator = "runge_kutta_4"
+step = 0.004
+end = { time = 0.01 }
+
+"#,
+        fixtures::rocket_toml!(),
+        r#"position = [0.0, 0.0, 100.0]
+rotation = [0.0, 0.3, 0.0]
+velocity = [30.0, 0.0, 5.0]
+angular_velocity = [0.0, 0.1, 0.0]
+"#
+    );
+
+    #[test]
+    fn loads_bodies() {
+        let sim = load_str(ROCKET).unwrap();
+
+        assert_eq!(sim.names, ["rocket"]);
+        assert_eq!(sim.integrator, Integrator::RungeKutta4);
+        assert_eq!(sim.step, Duration::from_millis(4));
+
+        let state = &sim.states[0];
+        assert_eq!(state.panels.len(), 2);
+        assert_eq!(state.transform.translation, Translation::new(0., 0., 100.));
+        assert_relative_eq!(state.velocity().linear.0, Vec3::new(30., 0., 5.));
+        assert_relative_eq!(
+            state.velocity().angular.0,
+            Vec3::new(0., 0.1, 0.),
+            epsilon = 1e-15
+        );
+    }
+
+    #[test]
+    fn runs_until_end_time() {
+        let mut sim = load_str(ROCKET).unwrap();
+        let mut expected = sim.states[0].clone();
+        sim.run();
+
+        assert_eq!(sim.steps, 3);
+        assert_eq!(sim.time, Duration::from_millis(10));
+        assert!(!sim.step());
+
+        expected.runge_kutta_4(Duration::from_millis(4));
+        expected.runge_kutta_4(Duration::from_millis(4));
+        expected.runge_kutta_4(Duration::from_millis(2));
+        assert_eq!(sim.states[0], expected);
+    }
+
+    #[test]
+    fn runs_step_count() {
+        let source = ROCKET.replace("{ time = 0.01 }", "{ steps = 5 }");
+        let mut sim = load_str(&source).unwrap();
+        sim.run();
+
+        assert_eq!(sim.steps, 5);
+        assert_eq!(sim.time, Duration::from_millis(20));
+    }
+
+    #[test]
+    fn parse_error_location() {
+        let source = ROCKET.replace("mass = 2.0", "mass = \"heavy\"");
+        let error = load_str(&source).unwrap_err();
+
+        assert!(matches!(error, ScenarioError::Parse { .. }));
+        assert_eq!(error.location(), Some(Location { line: 8, column: 8 }));
+    }
+
+    #[test]
+    fn invalid_step_location() {
+        let source = ROCKET.replace("step = 0.004", "step = -1.0");
+        let error = load_str(&source).unwrap_err();
+
+        assert!(matches!(error, ScenarioError::InvalidStep { .. }));
+        assert_eq!(error.location(), Some(Location { line: 3, column: 8 }));
+    }
+
+    #[test]
+    fn invalid_panel_location() {
+        let source = ROCKET.replace("normal = [0.0, 0.0, 1.0]", "normal = [0.0, 0.0, 0.0]");
+        let error = load_str(&source).unwrap_err();
+
+        assert!(matches!(
+            error,
+            ScenarioError::Body {
+                index: 0,
+                error: BuildError::ZeroPanelNormal(1),
+                ..
+            }
+        ));
+        assert_eq!(
+            error.location(),
+            Some(Location {
+                line: 12,
+                column: 5
+            })
+        );
+        assert_eq!(
+            error.to_string(),
+            "line 12, column 5: body 0: panel 1 has a zero length or non-finite normal"
+        );
+    }
+
+    #[test]
+    fn build_without_source() {
+        let mut scenario = Scenario::from_toml(ROCKET).unwrap();
+        scenario.bodies[0].mass = -2.;
+
+        let error = scenario.build().unwrap_err();
+        assert!(matches!(
+            error,
+            ScenarioError::Body {
+                error: BuildError::InvalidMass,
+                location: None,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn unvalidated_end() {
+        let mut sim = load_str(ROCKET).unwrap();
+
+        sim.end = EndCondition::Time(f64::NAN);
+        assert!(!sim.step());
+        sim.end = EndCondition::Time(-1.);
+        assert!(sim.is_finished());
+        sim.end = EndCondition::Time(f64::INFINITY);
+        assert!(sim.step());
+    }
+}
@@ -0,0 +1,542 @@
+//! Single precision mirrors of the quantity types.
+//!
+//! Every type in this module has the same name as its [f64] counterpart elsewhere in the crate,
+//! but wraps glam's [f32] types instead. The vector and scalar quantities share the arithmetic of
+//! their counterparts, including the products relating them. The composite types only go as far
+//! as storing state: [Rotation] composes with `+`, while [Transform], [Velocity], [Momentum] and
+//! [Moment] have no operators, and there is no single precision [State](crate::State).
+//!
+//! They are meant for consumers that store or render state in single precision, such as game
+//! engines and embedded targets. Integration always happens in double precision, convert at the
+//! boundary with [From]:
+//!
+//! ```
+//! use simscript_physics::{single, transform::{Transform, Translation}};
+//!
+//! let transform = single::Transform::from(Transform::from_translation(Translation::X));
+//! assert_eq!(transform.translation, single::Translation::X);
+//! assert_eq!(Transform::from(transform), Transform::from_translation(Translation::X));
+//! ```
+//!
+//! Conversions to single precision round to the nearest [f32], conversions back are exact.
+
+#![allow(clippy::suspicious_arithmetic_impl)]
+
+#[cfg(feature = "approx")]
+use approx::{AbsDiffEq, RelativeEq, UlpsEq};
+
+use crate::overload::overload;
+use crate::quantity::{
+    common_ops, scalar_ops, scalar_product, tensor_product, time_product, vector_ops,
+    vector_quantity,
+};
+use core::time::Duration;
+use glam::{Mat3, Quat, Vec3};
+
+vector_quantity! {
+    /// Single precision [Force](crate::moments::Force).
+    pub struct Force;
+    "force";
+    f32
+}
+
+vector_quantity! {
+    /// Single precision [Torque](crate::moments::Torque).
+    pub struct Torque;
+    "torque";
+    f32
+}
+
+vector_quantity! {
+    /// Single precision [LinVel](crate::velocity::LinVel).
+    pub struct LinVel;
+    "linear velocity";
+    f32
+}
+
+vector_quantity! {
+    /// Single precision [AngVel](crate::velocity::AngVel).
+    pub struct AngVel;
+    "angular velocity";
+    f32
+}
+
+vector_quantity! {
+    /// Single precision [LinMom](crate::momentum::LinMom).
+    pub struct LinMom;
+    "linear momentum";
+    f32
+}
+
+vector_quantity! {
+    /// Single precision [AngMom](crate::momentum::AngMom).
+    pub struct AngMom;
+    "angular momentum";
+    f32
+}
+
+vector_quantity! {
+    /// Single precision [LinAcc](crate::acceleration::LinAcc).
+    pub struct LinAcc;
+    "linear acceleration";
+    f32
+}
+
+vector_quantity! {
+    /// Single precision [AngAcc](crate::acceleration::AngAcc).
+    pub struct AngAcc;
+    "angular acceleration";
+    f32
+}
+
+/// Single precision [Translation](crate::transform::Translation).
+#[cfg_attr(feature = "approx", derive(approx_derive::Approx))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Translation(pub Vec3);
+
+impl Translation {
+    /// The zero translation vector (no displacement).
+    pub const ZERO: Self = Self::new(0., 0., 0.);
+
+    /// Unit translation along the X-axis.
+    pub const X: Self = Self::from_x(1.);
+    /// Unit translation along the Y-axis.
+    pub const Y: Self = Self::from_y(1.);
+    /// Unit translation along the Z-axis.
+    pub const Z: Self = Self::from_z(1.);
+
+    /// Creates a new [Translation] from individual components.
+    #[inline]
+    #[must_use]
+    pub const fn new(x: f32, y: f32, z: f32) -> Self {
+        Self(Vec3::new(x, y, z))
+    }
+
+    /// Creates a [Translation] from an existing [Vec3].
+    #[inline]
+    #[must_use]
+    pub const fn from_vec3(v: Vec3) -> Self {
+        Self(v)
+    }
+
+    /// Creates a [Translation] along the X-axis.
+    #[inline]
+    #[must_use]
+    pub const fn from_x(x: f32) -> Self {
+        Self::new(x, 0., 0.)
+    }
+
+    /// Creates a [Translation] along the Y-axis.
+    #[inline]
+    #[must_use]
+    pub const fn from_y(y: f32) -> Self {
+        Self::new(0., y, 0.)
+    }
+
+    /// Creates a [Translation] along the Z-axis.
+    #[inline]
+    #[must_use]
+    pub const fn from_z(z: f32) -> Self {
+        Self::new(0., 0., z)
+    }
+}
+
+vector_ops!(Translation, f32);
+
+/// Single precision [Rotation](crate::transform::Rotation).
+#[cfg_attr(feature = "approx", derive(approx_derive::Approx))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Rotation(pub Quat);
+
+impl Rotation {
+    /// The identity rotation.
+    pub const ZERO: Self = Self::new(Quat::IDENTITY);
+
+    /// Creates a new [Rotation] from the given quaternion.
+    #[inline]
+    #[must_use]
+    pub const fn new(quat: Quat) -> Self {
+        Self(quat)
+    }
+}
+
+impl AngVel {
+    /// Scales the velocity by a time duration in seconds, returning a [Rotation].
+    #[inline]
+    #[must_use]
+    pub fn mul_secs(&self, rhs: f32) -> Rotation {
+        Rotation::new(Quat::from_scaled_axis(self.0 * rhs))
+    }
+
+    /// Scales the velocity by a [Duration] returning a [Rotation].
+    #[inline]
+    #[must_use]
+    pub fn mul_dur(&self, rhs: &Duration) -> Rotation {
+        self.mul_secs(rhs.as_secs_f32())
+    }
+}
+
+overload!((a: ?Rotation) + (b: ?Rotation) -> Rotation { Rotation(b.0 * a.0) });
+overload!((a: ?AngVel) * (b: Duration) -> Rotation { a.mul_dur(&b) });
+overload!((a: ?AngVel) * (b: &Duration) -> Rotation { a.mul_dur(b) });
+
+/// Single precision [Transform](crate::transform::Transform).
+#[cfg_attr(feature = "approx", derive(approx_derive::Approx))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Transform {
+    /// The translation component of the transformation
+    pub translation: Translation,
+    /// The rotation component of the transformation
+    pub rotation: Rotation,
+}
+
+impl Transform {
+    /// A constant representing a zero transformation (no translation, identity rotation).
+    pub const ZERO: Self = Self::new(Translation::ZERO, Rotation::ZERO);
+
+    /// Creates a new transformation from translation and rotation components.
+    #[inline]
+    #[must_use]
+    pub const fn new(lin: Translation, ang: Rotation) -> Self {
+        Self {
+            translation: lin,
+            rotation: ang,
+        }
+    }
+
+    /// Maps a point from the child frame into the parent frame.
+    #[inline]
+    #[must_use]
+    pub fn transform_point(&self, point: Vec3) -> Vec3 {
+        self.rotation.0.mul_vec3(point) + self.translation.0
+    }
+
+    /// Maps a direction from the child frame into the parent frame.
+    #[inline]
+    #[must_use]
+    pub fn transform_vector(&self, vector: Vec3) -> Vec3 {
+        self.rotation.0.mul_vec3(vector)
+    }
+}
+
+/// Single precision [Velocity](crate::velocity::Velocity).
+#[cfg_attr(feature = "approx", derive(approx_derive::Approx))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Velocity {
+    /// Linear velocity component.
+    pub linear: LinVel,
+    /// Angular velocity component.
+    pub angular: AngVel,
+}
+
+impl Velocity {
+    /// Zero velocity constant.
+    pub const ZERO: Self = Self::new(LinVel::ZERO, AngVel::ZERO);
+
+    /// Creates a new [Velocity] from linear and angular components.
+    #[inline]
+    #[must_use]
+    pub const fn new(linear: LinVel, angular: AngVel) -> Self {
+        Self { linear, angular }
+    }
+}
+
+/// Single precision [Momentum](crate::momentum::Momentum).
+#[cfg_attr(feature = "approx", derive(approx_derive::Approx))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Momentum {
+    /// Linear momentum component.
+    pub linear: LinMom,
+    /// Angular momentum component.
+    pub angular: AngMom,
+}
+
+impl Momentum {
+    /// Zero momentum constant.
+    pub const ZERO: Self = Self::new(LinMom::ZERO, AngMom::ZERO);
+
+    /// Creates a new [Momentum] from linear and angular components.
+    #[inline]
+    #[must_use]
+    pub const fn new(linear: LinMom, angular: AngMom) -> Self {
+        Self { linear, angular }
+    }
+}
+
+/// Single precision [Moment](crate::moments::Moment).
+#[cfg_attr(feature = "approx", derive(approx_derive::Approx))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Moment {
+    /// Force component.
+    pub force: Force,
+    /// Torque component.
+    pub torque: Torque,
+}
+
+impl Moment {
+    /// Zero moment constant.
+    pub const ZERO: Self = Self::new(Force::ZERO, Torque::ZERO);
+
+    /// Creates a new [Moment] from force and torque components.
+    #[inline]
+    #[must_use]
+    pub const fn new(force: Force, torque: Torque) -> Self {
+        Self { force, torque }
+    }
+}
+
+/// Single precision [Mass](crate::inertia_mass::Mass).
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Mass(pub f32);
+
+impl Mass {
+    /// Zero mass constant.
+    pub const ZERO: Self = Self::new(0.);
+
+    /// Creates a new [Mass] from a value in kilograms.
+    #[inline]
+    #[must_use]
+    pub const fn new(mass: f32) -> Self {
+        Self(mass)
+    }
+}
+
+scalar_ops!(Mass, f32);
+
+/// Single precision [Inertia](crate::inertia_mass::Inertia).
+#[cfg_attr(feature = "approx", derive(approx_derive::Approx))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Inertia(pub Mat3);
+
+impl Inertia {
+    /// The inertia of a point mass at the origin, or of no mass at all.
+    pub const ZERO: Self = Self::new(Mat3::ZERO);
+
+    /// Creates a new [Inertia] from a tensor in kg·m².
+    #[inline]
+    #[must_use]
+    pub const fn new(inertia: Mat3) -> Self {
+        Self(inertia)
+    }
+}
+
+common_ops!(Inertia, f32);
+
+/// Single precision [Energy](crate::energy::Energy).
+#[cfg_attr(feature = "approx", derive(approx_derive::Approx))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Energy(pub f32);
+
+impl Energy {
+    /// Zero energy constant.
+    pub const ZERO: Self = Self::new(0.);
+
+    /// Creates a new [Energy] from a value in joules.
+    #[inline]
+    #[must_use]
+    pub const fn new(joules: f32) -> Self {
+        Self(joules)
+    }
+}
+
+scalar_ops!(Energy, f32);
+
+/// Single precision [Power](crate::energy::Power).
+#[cfg_attr(feature = "approx", derive(approx_derive::Approx))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Power(pub f32);
+
+impl Power {
+    /// Zero power constant.
+    pub const ZERO: Self = Self::new(0.);
+
+    /// Creates a new [Power] from a value in watts.
+    #[inline]
+    #[must_use]
+    pub const fn new(watts: f32) -> Self {
+        Self(watts)
+    }
+}
+
+scalar_ops!(Power, f32);
+
+// Same relation table as the double precision types.
+
+time_product!(LinVel * Duration = Translation; f32, as_secs_f32);
+time_product!(LinAcc * Duration = LinVel; f32, as_secs_f32);
+time_product!(AngAcc * Duration = AngVel; f32, as_secs_f32);
+time_product!(Force * Duration = LinMom; f32, as_secs_f32);
+time_product!(Torque * Duration = AngMom; f32, as_secs_f32);
+time_product!(Power * Duration = Energy; f32, as_secs_f32);
+
+scalar_product!(Mass * LinVel = LinMom);
+scalar_product!(Mass * LinAcc = Force);
+
+tensor_product!(Inertia * AngVel = AngMom);
+tensor_product!(Inertia * AngAcc = Torque);
+
+/// Conversions between the single and double precision version of a newtype quantity.
+macro_rules! precision_conversions {
+    ($($name:ident: $double:path => $narrow:ident, $widen:ident;)*) => {$(
+        impl From<$double> for $name {
+            #[inline]
+            fn from(value: $double) -> Self {
+                Self(value.0.$narrow())
+            }
+        }
+
+        impl From<$name> for $double {
+            #[inline]
+            fn from(value: $name) -> Self {
+                Self(value.0.$widen())
+            }
+        }
+    )*};
+}
+
+precision_conversions! {
+    Force: crate::moments::Force => as_vec3, as_dvec3;
+    Torque: crate::moments::Torque => as_vec3, as_dvec3;
+    LinVel: crate::velocity::LinVel => as_vec3, as_dvec3;
+    AngVel: crate::velocity::AngVel => as_vec3, as_dvec3;
+    LinMom: crate::momentum::LinMom => as_vec3, as_dvec3;
+    AngMom: crate::momentum::AngMom => as_vec3, as_dvec3;
+    LinAcc: crate::acceleration::LinAcc => as_vec3, as_dvec3;
+    AngAcc: crate::acceleration::AngAcc => as_vec3, as_dvec3;
+    Translation: crate::transform::Translation => as_vec3, as_dvec3;
+    Rotation: crate::transform::Rotation => as_quat, as_dquat;
+    Inertia: crate::inertia_mass::Inertia => as_mat3, as_dmat3;
+}
+
+/// Conversions between the single and double precision version of a scalar quantity.
+macro_rules! scalar_precision_conversions {
+    ($($name:ident: $double:path;)*) => {$(
+        impl From<$double> for $name {
+            #[inline]
+            fn from(value: $double) -> Self {
+                Self(value.0 as f32)
+            }
+        }
+
+        impl From<$name> for $double {
+            #[inline]
+            fn from(value: $name) -> Self {
+                Self(f64::from(value.0))
+            }
+        }
+    )*};
+}
+
+scalar_precision_conversions! {
+    Mass: crate::inertia_mass::Mass;
+    Energy: crate::energy::Energy;
+    Power: crate::energy::Power;
+}
+
+/// Conversions between the single and double precision version of a composite quantity.
+macro_rules! composite_precision_conversions {
+    ($($name:ident: $double:path { $($field:ident),* };)*) => {$(
+        impl From<$double> for $name {
+            #[inline]
+            fn from(value: $double) -> Self {
+                Self { $($field: value.$field.into()),* }
+            }
+        }
+
+        impl From<$name> for $double {
+            #[inline]
+            fn from(value: $name) -> Self {
+                Self { $($field: value.$field.into()),* }
+            }
+        }
+    )*};
+}
+
+composite_precision_conversions! {
+    Transform: crate::transform::Transform { translation, rotation };
+    Velocity: crate::velocity::Velocity { linear, angular };
+    Momentum: crate::momentum::Momentum { linear, angular };
+    Moment: crate::moments::Moment { force, torque };
+}
+
+#[cfg(test)]
+mod conversions {
+    use super::*;
+    use approx::assert_ulps_eq;
+    use glam::{DMat3, DQuat, DVec3};
+
+    #[test]
+    fn round_trip() {
+        let force = crate::moments::Force::new(1.5, -2.25, 3.);
+        let rotation = crate::transform::Rotation::new(DQuat::from_rotation_y(0.5));
+        let inertia = crate::inertia_mass::Inertia::new(DMat3::from_diagonal(DVec3::ONE * 2.));
+
+        assert_eq!(crate::moments::Force::from(Force::from(force)), force);
+        assert_ulps_eq!(Rotation::from(rotation).0, Quat::from_rotation_y(0.5));
+        assert_eq!(
+            Inertia::from(inertia).0,
+            Mat3::from_diagonal(Vec3::splat(2.))
+        );
+        assert_eq!(crate::energy::Energy::from(Energy::new(0.5)).0, 0.5);
+    }
+
+    #[test]
+    fn rounds_to_nearest() {
+        let lin = crate::velocity::LinVel::with_x(0.1);
+        let single = LinVel::from(lin);
+
+        assert_eq!(single, LinVel::with_x(0.1));
+        assert_ulps_eq!(
+            crate::velocity::LinVel::from(single).0.x,
+            0.1,
+            epsilon = 1e-8
+        );
+    }
+
+    #[test]
+    fn composite() {
+        let transform = crate::transform::Transform::new(
+            crate::transform::Translation::new(1., 2., 3.),
+            crate::transform::Rotation::new(DQuat::from_rotation_z(1.)),
+        );
+        let single = Transform::from(transform);
+
+        assert_eq!(single.translation, Translation::new(1., 2., 3.));
+        assert_ulps_eq!(
+            single.transform_point(Vec3::X),
+            transform.transform_point(DVec3::X).as_vec3()
+        );
+    }
+}
+
+#[cfg(test)]
+mod relations {
+    use super::*;
+    use approx::assert_ulps_eq;
+
+    #[test]
+    fn same_as_double() {
+        let dur = Duration::from_millis(250);
+        let m = Mass::new(2.);
+
+        assert_ulps_eq!(Force::X * dur, LinMom::with_x(0.25));
+        assert_ulps_eq!(m * LinAcc::Y, Force::with_y(2.));
+        assert_ulps_eq!(LinMom::Z / m, LinVel::with_z(0.5));
+        assert_ulps_eq!(
+            Inertia::new(Mat3::from_diagonal(Vec3::splat(4.))) * AngVel::ONE,
+            AngMom::splat(4.)
+        );
+        assert_ulps_eq!((AngVel::Z * dur).0, Quat::from_rotation_z(0.25));
+        assert_ulps_eq!(Energy::new(1.) / dur, Power::new(4.));
+    }
+}
@@ -0,0 +1,311 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::Rotation;
+use crate::math;
+use core::f64::consts::PI;
+use glam::{DQuat as Quat, EulerRot};
+
+/// How close to a singular configuration the middle angle of an Euler sequence has to be for it
+/// to be treated as gimbal locked.
+///
+/// Compared against `|cos(second)|` for three-axis sequences and `|sin(second)|` for two-axis
+/// sequences.
+pub const GIMBAL_LOCK_TOLERANCE: f64 = 1e-8;
+
+/// A set of Euler angles together with the sequence they are applied in.
+///
+/// Produced by [Rotation::to_euler] and friends. In a gimbal locked configuration the first and
+/// third rotation axes line up, so only their combined angle is known. In that case
+/// [EulerAngles::gimbal_lock] is set, [EulerAngles::third] is zero and the whole combined angle is
+/// stored in [EulerAngles::first].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EulerAngles {
+    /// The rotation sequence the angles are applied in.
+    pub order: EulerRot,
+    /// Angle of the first rotation in radians.
+    pub first: f64,
+    /// Angle of the second (middle) rotation in radians.
+    pub second: f64,
+    /// Angle of the third rotation in radians.
+    pub third: f64,
+    /// Whether the rotation was gimbal locked when the angles were extracted.
+    pub gimbal_lock: bool,
+}
+
+impl EulerAngles {
+    /// Creates a new set of Euler angles, computing whether they are gimbal locked.
+    ///
+    /// # Arguments
+    /// * `order` - The rotation sequence ([EulerRot])
+    /// * `first`, `second`, `third` - The angles in radians
+    ///
+    /// # Returns
+    /// A new [EulerAngles]
+    #[inline]
+    #[must_use]
+    pub fn new(order: EulerRot, first: f64, second: f64, third: f64) -> Self {
+        Self {
+            order,
+            first,
+            second,
+            third,
+            gimbal_lock: is_gimbal_locked(order, second),
+        }
+    }
+}
+
+/// Returns `true` for sequences rotating about the fixed axes of the original frame.
+const fn is_extrinsic(order: EulerRot) -> bool {
+    use EulerRot::*;
+    matches!(
+        order,
+        ZYXEx
+            | ZXYEx
+            | YXZEx
+            | YZXEx
+            | XYZEx
+            | XZYEx
+            | ZYZEx
+            | ZXZEx
+            | YXYEx
+            | YZYEx
+            | XYXEx
+            | XZXEx
+    )
+}
+
+/// Returns `true` for proper Euler sequences, where the first and third axis are the same.
+const fn is_proper(order: EulerRot) -> bool {
+    use EulerRot::*;
+    matches!(
+        order,
+        ZYZ | ZXZ | YXY | YZY | XYX | XZX | ZYZEx | ZXZEx | YXYEx | YZYEx | XYXEx | XZXEx
+    )
+}
+
+fn is_gimbal_locked(order: EulerRot, second: f64) -> bool {
+    if is_proper(order) {
+        math::sin(second).abs() < GIMBAL_LOCK_TOLERANCE
+    } else {
+        math::cos(second).abs() < GIMBAL_LOCK_TOLERANCE
+    }
+}
+
+/// Wraps an angle into the range `(-π, π]`.
+fn wrap_angle(angle: f64) -> f64 {
+    let wrapped = math::rem_euclid(angle + PI, 2. * PI) - PI;
+    if wrapped == -PI {
+        PI
+    } else {
+        wrapped
+    }
+}
+
+/// Euler angle conversions
+impl Rotation {
+    /// Creates a [Rotation] from three angles applied in the given sequence.
+    ///
+    /// # Arguments
+    /// * `order` - The rotation sequence ([EulerRot])
+    /// * `a`, `b`, `c` - The angles in radians, in the order given by `order`
+    ///
+    /// # Returns
+    /// A new [Rotation] representing the combined rotation
+    #[inline]
+    #[must_use]
+    pub fn from_euler(order: EulerRot, a: f64, b: f64, c: f64) -> Self {
+        Self::new(Quat::from_euler(order, a, b, c))
+    }
+
+    /// Creates a [Rotation] from a set of [EulerAngles].
+    #[inline]
+    #[must_use]
+    pub fn from_euler_angles(angles: &EulerAngles) -> Self {
+        Self::from_euler(angles.order, angles.first, angles.second, angles.third)
+    }
+
+    /// Decomposes the rotation into Euler angles using the given sequence.
+    ///
+    /// The middle angle is in `[-π/2, π/2]` for three-axis sequences and `[0, π]` for two-axis
+    /// sequences, the other two are in `(-π, π]`. Near gimbal lock (see [GIMBAL_LOCK_TOLERANCE])
+    /// the third angle is set to zero and the first angle is solved for so that the angles still
+    /// reproduce the rotation.
+    ///
+    /// # Arguments
+    /// * `order` - The rotation sequence ([EulerRot])
+    ///
+    /// # Returns
+    /// The [EulerAngles] of the rotation
+    #[must_use]
+    pub fn to_euler(&self, order: EulerRot) -> EulerAngles {
+        let (mut a, mut b, mut c) = self.0.to_euler(order);
+
+        // Every proper Euler rotation has a second solution with the middle angle negated, always
+        // report the one with the middle angle in `[0, π]`
+        if is_proper(order) && b < 0. {
+            (a, b, c) = (wrap_angle(a + PI), -b, wrap_angle(c + PI));
+        }
+
+        if !is_gimbal_locked(order, b) {
+            return EulerAngles::new(order, a, b, c);
+        }
+
+        // With the third angle fixed at zero the first rotation is whatever remains once the
+        // middle rotation has been removed
+        let middle = Quat::from_euler(order, 0., b, 0.);
+        let first = if is_extrinsic(order) {
+            middle.inverse() * self.0
+        } else {
+            self.0 * middle.inverse()
+        };
+        let (axis, _) = Quat::from_euler(order, 1., 0., 0.).to_axis_angle();
+        let a = 2. * math::atan2(first.xyz().dot(axis), first.w);
+
+        EulerAngles {
+            order,
+            first: wrap_angle(a),
+            second: b,
+            third: 0.,
+            gimbal_lock: true,
+        }
+    }
+
+    /// Creates a [Rotation] from aerospace yaw, pitch and roll angles.
+    ///
+    /// Uses the intrinsic Z-Y-X sequence with the body x-axis pointing forward, y-axis to the right
+    /// and z-axis down: first yaw around z, then pitch around the new y-axis and finally roll
+    /// around the new x-axis.
+    ///
+    /// # Arguments
+    /// * `yaw` - Heading angle in radians
+    /// * `pitch` - Elevation angle in radians
+    /// * `roll` - Bank angle in radians
+    ///
+    /// # Returns
+    /// A new [Rotation] representing the attitude
+    #[inline]
+    #[must_use]
+    pub fn from_yaw_pitch_roll(yaw: f64, pitch: f64, roll: f64) -> Self {
+        Self::from_euler(EulerRot::ZYX, yaw, pitch, roll)
+    }
+
+    /// Decomposes the rotation into aerospace yaw, pitch and roll angles.
+    ///
+    /// See [Rotation::from_yaw_pitch_roll] for the convention used. At ±90° pitch the rotation is
+    /// gimbal locked, roll is reported as zero and the combined angle is returned as yaw.
+    ///
+    /// # Returns
+    /// [EulerAngles] with yaw, pitch and roll as the first, second and third angle
+    #[inline]
+    #[must_use]
+    pub fn to_yaw_pitch_roll(&self) -> EulerAngles {
+        self.to_euler(EulerRot::ZYX)
+    }
+}
+
+/// Implements conversion from [EulerAngles] to [Rotation].
+impl From<EulerAngles> for Rotation {
+    #[inline]
+    fn from(value: EulerAngles) -> Self {
+        Self::from_euler_angles(&value)
+    }
+}
+
+/// Implements conversion from [Rotation] to aerospace yaw, pitch and roll [EulerAngles].
+impl From<Rotation> for EulerAngles {
+    #[inline]
+    fn from(value: Rotation) -> Self {
+        value.to_yaw_pitch_roll()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_ulps_eq;
+    use rstest::rstest;
+    use rstest_reuse::{apply, template};
+    use std::f64::consts::FRAC_PI_2;
+
+    #[template]
+    #[rstest]
+    #[case(EulerRot::ZYX)]
+    #[case(EulerRot::ZXY)]
+    #[case(EulerRot::YXZ)]
+    #[case(EulerRot::YZX)]
+    #[case(EulerRot::XYZ)]
+    #[case(EulerRot::XZY)]
+    #[case(EulerRot::ZYZ)]
+    #[case(EulerRot::ZXZ)]
+    #[case(EulerRot::YXY)]
+    #[case(EulerRot::YZY)]
+    #[case(EulerRot::XYX)]
+    #[case(EulerRot::XZX)]
+    #[case(EulerRot::ZYXEx)]
+    #[case(EulerRot::XYZEx)]
+    #[case(EulerRot::ZXZEx)]
+    #[case(EulerRot::XZXEx)]
+    fn orders(#[case] order: EulerRot) {}
+
+    /// Quaternions `q` and `-q` are the same rotation
+    fn assert_same_rotation(a: Rotation, b: Rotation) {
+        assert!(
+            a.0.dot(b.0).abs() > 1. - 1e-12,
+            "{a:?} and {b:?} are different rotations"
+        );
+    }
+
+    #[apply(orders)]
+    fn round_trip(order: EulerRot) {
+        let (a, b, c) = if is_proper(order) {
+            (0.3, 1.1, -0.7)
+        } else {
+            (0.3, -0.5, 2.1)
+        };
+
+        let r = Rotation::from_euler(order, a, b, c);
+        let angles = r.to_euler(order);
+
+        assert!(!angles.gimbal_lock);
+        assert_ulps_eq!(angles.first, a, epsilon = 1e-12);
+        assert_ulps_eq!(angles.second, b, epsilon = 1e-12);
+        assert_ulps_eq!(angles.third, c, epsilon = 1e-12);
+        assert_same_rotation(angles.into(), r);
+    }
+
+    #[apply(orders)]
+    fn gimbal_lock(order: EulerRot) {
+        let b = if is_proper(order) { 0. } else { FRAC_PI_2 };
+
+        let r = Rotation::from_euler(order, 0.4, b, 0.25);
+        let angles = r.to_euler(order);
+
+        assert!(angles.gimbal_lock);
+        assert_eq!(angles.third, 0.);
+        assert_same_rotation(angles.into(), r);
+    }
+
+    #[test]
+    fn yaw_pitch_roll() {
+        let r = Rotation::from_yaw_pitch_roll(0.5, 0.2, -0.1);
+        let expected = Rotation::from_x(-0.1) + Rotation::from_y(0.2) + Rotation::from_z(0.5);
+
+        assert_ulps_eq!(r.0, expected.0);
+
+        let angles = r.to_yaw_pitch_roll();
+        assert_ulps_eq!(angles.first, 0.5, epsilon = 1e-12);
+        assert_ulps_eq!(angles.second, 0.2, epsilon = 1e-12);
+        assert_ulps_eq!(angles.third, -0.1, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn yaw_pitch_roll_straight_up() {
+        let r = Rotation::from_yaw_pitch_roll(0.3, -FRAC_PI_2, 0.6);
+        let angles = EulerAngles::from(r);
+
+        assert!(angles.gimbal_lock);
+        assert_same_rotation(Rotation::from(angles), r);
+    }
+}
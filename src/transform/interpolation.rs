@@ -0,0 +1,327 @@
+use super::{Rotation, Transform, Translation};
+use crate::math;
+use crate::overload::overload;
+use crate::velocity::AngVel;
+use core::time::Duration;
+use glam::{DQuat as Quat, DVec3 as Vec3};
+
+/// Returns the logarithm of a unit quaternion as a vector, half its rotation vector.
+#[inline]
+fn log(q: Quat) -> Vec3 {
+    q.to_scaled_axis() / 2.
+}
+
+/// Returns the unit quaternion whose logarithm is `v`.
+#[inline]
+fn exp(v: Vec3) -> Quat {
+    Quat::from_scaled_axis(v * 2.)
+}
+
+/// Flips `q` into the same hemisphere as `reference`, so that interpolating between them takes the
+/// shortest path.
+#[inline]
+fn closest(q: Quat, reference: Quat) -> Quat {
+    if q.dot(reference) < 0. {
+        -q
+    } else {
+        q
+    }
+}
+
+/// Interpolation and distance metrics
+impl Rotation {
+    /// Spherically interpolates between two rotations along the shortest path.
+    ///
+    /// # Arguments
+    /// * `end` - The rotation at `t = 1`
+    /// * `t` - Interpolation factor, usually between zero and one
+    ///
+    /// # Returns
+    /// A [Rotation] rotating at a constant rate from `self` to `end`
+    #[inline]
+    #[must_use]
+    pub fn slerp(&self, end: &Self, t: f64) -> Self {
+        Self(self.0.slerp(end.0, t))
+    }
+
+    /// Linearly interpolates between two rotations along the shortest path and normalizes the
+    /// result.
+    ///
+    /// Cheaper than [Rotation::slerp] but the rate of rotation is not constant.
+    ///
+    /// # Arguments
+    /// * `end` - The rotation at `t = 1`
+    /// * `t` - Interpolation factor, usually between zero and one
+    ///
+    /// # Returns
+    /// A normalized [Rotation] between `self` and `end`
+    #[inline]
+    #[must_use]
+    pub fn nlerp(&self, end: &Self, t: f64) -> Self {
+        Self(self.0.lerp(end.0, t))
+    }
+
+    /// Computes the inner control point for [Rotation::squad] at `self`.
+    ///
+    /// # Arguments
+    /// * `prev` - The keyframe before `self`
+    /// * `next` - The keyframe after `self`
+    ///
+    /// # Returns
+    /// The control point giving a smooth rate of rotation through `self`
+    #[must_use]
+    pub fn squad_control(&self, prev: &Self, next: &Self) -> Self {
+        let q = self.0;
+        let inv = q.inverse();
+        let to_next = log(closest(inv * next.0, Quat::IDENTITY));
+        let to_prev = log(closest(inv * prev.0, Quat::IDENTITY));
+
+        Self(q * exp(-(to_next + to_prev) / 4.))
+    }
+
+    /// Spherical cubic interpolation between `self` and `end`.
+    ///
+    /// Gives a smooth rate of rotation across a whole sequence of keyframes, when the control
+    /// points are computed with [Rotation::squad_control].
+    ///
+    /// # Arguments
+    /// * `ctrl_start` - The control point at `self`
+    /// * `ctrl_end` - The control point at `end`
+    /// * `end` - The rotation at `t = 1`
+    /// * `t` - Interpolation factor between zero and one
+    ///
+    /// # Returns
+    /// A [Rotation] between `self` and `end`
+    #[must_use]
+    pub fn squad(&self, ctrl_start: &Self, ctrl_end: &Self, end: &Self, t: f64) -> Self {
+        let outer = self.0.slerp(end.0, t);
+        let inner = ctrl_start.0.slerp(ctrl_end.0, t);
+        Self(outer.slerp(inner, 2. * t * (1. - t)))
+    }
+
+    /// Returns the geodesic distance between two rotations.
+    ///
+    /// # Returns
+    /// The angle in radians, within `[0, π]`, of the smallest rotation between `self` and `other`
+    #[inline]
+    #[must_use]
+    pub fn angle_to(&self, other: &Self) -> f64 {
+        // atan2 stays accurate for small angles, where acos of the dot product does not
+        let delta = self.0.inverse() * other.0;
+        2. * math::atan2(delta.xyz().length(), delta.w.abs())
+    }
+
+    /// Returns the constant [AngVel] that rotates `self` into `target` over `rhs` seconds.
+    ///
+    /// This is the inverse of applying [AngVel::mul_secs] to `self`, so that
+    /// `self + self.ang_vel_to_secs(target, t).mul_secs(t)` equals `target`.
+    #[inline]
+    #[must_use]
+    pub fn ang_vel_to_secs(&self, target: &Self, rhs: f64) -> AngVel {
+        let delta = target.0 * self.0.inverse();
+        Self(delta).div_secs(rhs)
+    }
+
+    /// Returns the constant [AngVel] that rotates `self` into `target` over a [Duration].
+    ///
+    /// Note: this function uses [Rotation::ang_vel_to_secs] internally.
+    #[inline]
+    #[must_use]
+    pub fn ang_vel_to(&self, target: &Self, rhs: &Duration) -> AngVel {
+        self.ang_vel_to_secs(target, rhs.as_secs_f64())
+    }
+
+    /// Divides the rotation by a time in seconds, returning an [AngVel].
+    ///
+    /// This is the logarithmic map of the rotation and the inverse of [AngVel::mul_secs], taking
+    /// the shortest path.
+    #[inline]
+    #[must_use]
+    pub fn div_secs(&self, rhs: f64) -> AngVel {
+        AngVel(closest(self.0, Quat::IDENTITY).to_scaled_axis() / rhs)
+    }
+
+    /// Divides the rotation by a [Duration], returning an [AngVel].
+    ///
+    /// Inverse of [AngVel::mul_dur]. Note: this function uses [Rotation::div_secs] internally.
+    #[inline]
+    #[must_use]
+    pub fn div_dur(&self, rhs: &Duration) -> AngVel {
+        self.div_secs(rhs.as_secs_f64())
+    }
+}
+
+overload!((a: ?Rotation) / (b: Duration) -> AngVel { a.div_dur(&b) });
+overload!((a: ?Rotation) / (b: &Duration) -> AngVel { a.div_dur(b) });
+
+/// Interpolation
+///
+/// Translations are interpolated linearly alongside the rotation.
+impl Transform {
+    /// Interpolates between two transformations, using [Rotation::slerp] for the rotation.
+    ///
+    /// # Arguments
+    /// * `end` - The transformation at `t = 1`
+    /// * `t` - Interpolation factor, usually between zero and one
+    ///
+    /// # Returns
+    /// A [Transform] between `self` and `end`
+    #[inline]
+    #[must_use]
+    pub fn slerp(&self, end: &Self, t: f64) -> Self {
+        Self::new(
+            Translation(self.translation.0.lerp(end.translation.0, t)),
+            self.rotation.slerp(&end.rotation, t),
+        )
+    }
+
+    /// Interpolates between two transformations, using [Rotation::nlerp] for the rotation.
+    ///
+    /// # Arguments
+    /// * `end` - The transformation at `t = 1`
+    /// * `t` - Interpolation factor, usually between zero and one
+    ///
+    /// # Returns
+    /// A [Transform] between `self` and `end`
+    #[inline]
+    #[must_use]
+    pub fn nlerp(&self, end: &Self, t: f64) -> Self {
+        Self::new(
+            Translation(self.translation.0.lerp(end.translation.0, t)),
+            self.rotation.nlerp(&end.rotation, t),
+        )
+    }
+
+    /// Computes the inner control point for [Transform::squad] at `self`.
+    ///
+    /// The translation control point is the linear counterpart of [Rotation::squad_control].
+    #[must_use]
+    pub fn squad_control(&self, prev: &Self, next: &Self) -> Self {
+        let p = self.translation.0;
+        let to_next = next.translation.0 - p;
+        let to_prev = prev.translation.0 - p;
+
+        Self::new(
+            Translation(p - (to_next + to_prev) / 4.),
+            self.rotation.squad_control(&prev.rotation, &next.rotation),
+        )
+    }
+
+    /// Cubic interpolation between `self` and `end`, see [Rotation::squad].
+    ///
+    /// # Arguments
+    /// * `ctrl_start` - The control point at `self`
+    /// * `ctrl_end` - The control point at `end`
+    /// * `end` - The transformation at `t = 1`
+    /// * `t` - Interpolation factor between zero and one
+    ///
+    /// # Returns
+    /// A [Transform] between `self` and `end`
+    #[must_use]
+    pub fn squad(&self, ctrl_start: &Self, ctrl_end: &Self, end: &Self, t: f64) -> Self {
+        let outer = self.translation.0.lerp(end.translation.0, t);
+        let inner = ctrl_start.translation.0.lerp(ctrl_end.translation.0, t);
+
+        Self::new(
+            Translation(outer.lerp(inner, 2. * t * (1. - t))),
+            self.rotation
+                .squad(&ctrl_start.rotation, &ctrl_end.rotation, &end.rotation, t),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_ulps_eq;
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    fn assert_same_rotation(a: Rotation, b: Rotation) {
+        assert!(
+            a.0.dot(b.0).abs() > 1. - 1e-12,
+            "{a:?} and {b:?} are different rotations"
+        );
+    }
+
+    #[test]
+    fn slerp_nlerp() {
+        let a = Rotation::from_z(0.2);
+        let b = Rotation::from_z(1.4);
+
+        assert_same_rotation(a.slerp(&b, 0.5), Rotation::from_z(0.8));
+        assert_same_rotation(a.nlerp(&b, 0.5), Rotation::from_z(0.8));
+        assert_same_rotation(a.slerp(&b, 0.), a);
+        assert_same_rotation(a.slerp(&b, 1.), b);
+        assert_same_rotation(a.slerp(&b, 0.25), Rotation::from_z(0.5));
+    }
+
+    #[test]
+    fn slerp_shortest_path() {
+        let a = Rotation::from_z(-3.);
+        let b = Rotation(-Rotation::from_z(3.).0);
+
+        assert_same_rotation(a.slerp(&b, 0.5), Rotation::from_z(PI));
+    }
+
+    #[test]
+    fn squad_keyframes() {
+        let keys = [0.0, 0.5, 1.0, 1.5].map(Rotation::from_x);
+        let c1 = keys[1].squad_control(&keys[0], &keys[2]);
+        let c2 = keys[2].squad_control(&keys[1], &keys[3]);
+
+        assert_same_rotation(keys[1].squad(&c1, &c2, &keys[2], 0.), keys[1]);
+        assert_same_rotation(keys[1].squad(&c1, &c2, &keys[2], 1.), keys[2]);
+        // Evenly spaced keyframes around one axis interpolate at a constant rate
+        assert_same_rotation(
+            keys[1].squad(&c1, &c2, &keys[2], 0.5),
+            Rotation::from_x(0.75),
+        );
+    }
+
+    #[test]
+    fn angle_to() {
+        let a = Rotation::from_y(0.3);
+
+        assert_ulps_eq!(a.angle_to(&Rotation::from_y(1.3)), 1., epsilon = 1e-12);
+        assert_ulps_eq!(a.angle_to(&Rotation(-a.0)), 0.);
+        assert_ulps_eq!(
+            Rotation::ZERO.angle_to(&Rotation::from_x(-FRAC_PI_2)),
+            FRAC_PI_2,
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn log_map_inverts_mul_dur() {
+        let dur = Duration::from_millis(250);
+        let w = AngVel::new(0.4, -1.2, 2.);
+
+        assert_ulps_eq!(w.mul_dur(&dur) / dur, w, epsilon = 1e-12);
+
+        let from = Rotation::from_yaw_pitch_roll(0.3, 0.1, -0.4);
+        let to = Rotation::from_yaw_pitch_roll(-0.2, 0.5, 0.1);
+        let w = from.ang_vel_to(&to, &dur);
+
+        let mut r = from;
+        r += w * dur;
+        assert_same_rotation(r, to);
+    }
+
+    #[test]
+    fn transform() {
+        let a = Transform::new(Translation::ZERO, Rotation::from_z(0.));
+        let b = Transform::new(Translation::new(2., 4., 0.), Rotation::from_z(1.));
+
+        let mid = a.slerp(&b, 0.5);
+        assert_ulps_eq!(mid.translation.0, Vec3::new(1., 2., 0.));
+        assert_same_rotation(mid.rotation, Rotation::from_z(0.5));
+
+        let mid = a.nlerp(&b, 0.5);
+        assert_ulps_eq!(mid.translation.0, Vec3::new(1., 2., 0.));
+        assert_same_rotation(mid.rotation, Rotation::from_z(0.5));
+
+        let (ca, cb) = (a.squad_control(&a, &b), b.squad_control(&a, &b));
+        assert_ulps_eq!(a.squad(&ca, &cb, &b, 0.).translation, a.translation);
+        assert_ulps_eq!(a.squad(&ca, &cb, &b, 1.).translation, b.translation);
+    }
+}
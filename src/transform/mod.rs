@@ -6,13 +6,17 @@ use {
     approx_derive::Approx,
 };
 
+use crate::overload::overload;
+use core::iter::Sum;
 use glam::{DQuat as Quat, DVec3 as Vec3};
-use overload::overload;
-use std::{iter::Sum, ops};
 
+mod euler;
+mod interpolation;
 mod rotation;
 mod translation;
 
+pub use euler::{EulerAngles, GIMBAL_LOCK_TOLERANCE};
+pub use glam::EulerRot;
 pub use rotation::Rotation;
 pub use translation::Translation;
 
@@ -118,11 +122,79 @@ impl Transform {
     }
 }
 
+/// Rigid body (SE(3)) operations
+///
+/// A [Transform] maps positions from a child frame into its parent frame, first rotating by
+/// [Transform::rotation] and then translating by [Transform::translation]. Unlike the `+` and `-`
+/// operators, which treat both components independently and are used to accumulate small steps
+/// during integration, these functions follow proper rigid body semantics and can be used to chain
+/// frames.
+impl Transform {
+    /// Chains two transformations, applying `child` first and `self` second.
+    ///
+    /// If `self` places a parent frame in the world and `child` places a child frame relative to
+    /// the parent, the result places the child frame in the world.
+    ///
+    /// # Arguments
+    /// * `child` - The transformation relative to `self`
+    ///
+    /// # Returns
+    /// A [Transform] equivalent to applying `child` and then `self`
+    #[inline]
+    #[must_use]
+    pub fn compose(&self, child: &Self) -> Self {
+        Self::from_inner(
+            self.transform_point(child.translation.0),
+            self.rotation.0 * child.rotation.0,
+        )
+    }
+
+    /// Returns the inverse transformation.
+    ///
+    /// Composing a transformation with its inverse, in either order, results in [Transform::ZERO].
+    ///
+    /// # Returns
+    /// A [Transform] mapping positions from the parent frame back into the child frame
+    #[inline]
+    #[must_use]
+    pub fn inverse(&self) -> Self {
+        let rot = self.rotation.0.inverse();
+        Self::from_inner(rot.mul_vec3(-self.translation.0), rot)
+    }
+
+    /// Maps a position from the child frame into the parent frame.
+    ///
+    /// # Arguments
+    /// * `point` - The position in the child frame ([Vec3])
+    ///
+    /// # Returns
+    /// The rotated and translated position in the parent frame
+    #[inline]
+    #[must_use]
+    pub fn transform_point(&self, point: Vec3) -> Vec3 {
+        self.rotation.0.mul_vec3(point) + self.translation.0
+    }
+
+    /// Maps a direction from the child frame into the parent frame.
+    ///
+    /// Directions are only rotated, never translated.
+    ///
+    /// # Arguments
+    /// * `vector` - The direction in the child frame ([Vec3])
+    ///
+    /// # Returns
+    /// The rotated direction in the parent frame
+    #[inline]
+    #[must_use]
+    pub fn transform_vector(&self, vector: Vec3) -> Vec3 {
+        self.rotation.0.mul_vec3(vector)
+    }
+}
+
 /// Conversion from [Translation] to [Transform]
 impl From<Translation> for Transform {
     /// Converts a [Translation] into a [Transform] with zero rotation.
     #[inline]
-    #[must_use]
     fn from(value: Translation) -> Self {
         Self::from_translation(value)
     }
@@ -132,7 +204,6 @@ impl From<Translation> for Transform {
 impl From<Rotation> for Transform {
     /// Converts a [Rotation] into a [Transform] with zero translation.
     #[inline]
-    #[must_use]
     fn from(value: Rotation) -> Self {
         Self::from_rotation(value)
     }
@@ -150,7 +221,6 @@ impl Sum for Transform {
     /// # Returns
     /// The sum of all transformations in the iterator as a [Transform]
     #[inline]
-    #[must_use]
     fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
         iter.fold(Self::ZERO, |a, b| a + b)
     }
@@ -187,3 +257,61 @@ overload!(-(a: ?Transform) -> Transform {
         rotation: -a.rotation
     }
 });
+
+#[cfg(test)]
+mod rigid_body {
+    use super::*;
+    use approx::assert_ulps_eq;
+    use std::f64::consts::FRAC_PI_2;
+
+    fn airframe() -> Transform {
+        Transform::new(Translation::new(10., 0., 5.), Rotation::from_z(FRAC_PI_2))
+    }
+
+    fn motor_mount() -> Transform {
+        Transform::new(Translation::new(-1., 0., 0.), Rotation::from_y(FRAC_PI_2))
+    }
+
+    #[test]
+    fn transform_point_and_vector() {
+        let t = airframe();
+
+        assert_ulps_eq!(t.transform_point(Vec3::X), Vec3::new(10., 1., 5.));
+        assert_ulps_eq!(t.transform_vector(Vec3::X), Vec3::Y);
+        assert_ulps_eq!(t.transform_point(Vec3::ZERO), t.translation.0);
+    }
+
+    #[test]
+    fn compose() {
+        let (parent, child) = (airframe(), motor_mount());
+        let chained = parent.compose(&child);
+
+        for p in [Vec3::ZERO, Vec3::X, Vec3::new(0.3, -2., 7.)] {
+            assert_ulps_eq!(
+                chained.transform_point(p),
+                parent.transform_point(child.transform_point(p))
+            );
+        }
+        assert_ulps_eq!(chained.translation.0, Vec3::new(10., -1., 5.));
+        assert_ulps_eq!(Transform::ZERO.compose(&child), child);
+        assert_ulps_eq!(child.compose(&Transform::ZERO), child);
+    }
+
+    #[test]
+    fn inverse() {
+        let t = airframe().compose(&motor_mount());
+        let p = Vec3::new(1.5, -3., 0.25);
+
+        assert_ulps_eq!(
+            t.inverse().transform_point(t.transform_point(p)),
+            p,
+            epsilon = 1e-14
+        );
+        assert_ulps_eq!(
+            t.compose(&t.inverse()).translation.0,
+            Vec3::ZERO,
+            epsilon = 1e-14
+        );
+        assert_ulps_eq!(t.inverse().compose(&t).rotation.0, Quat::IDENTITY);
+    }
+}
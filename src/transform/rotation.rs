@@ -10,9 +10,9 @@ use {
 };
 
 use super::Transform;
-use glam::DQuat as Quat;
-use overload::overload;
-use std::{iter::Sum, ops};
+use crate::overload::overload;
+use core::iter::Sum;
+use glam::{DMat3 as Mat3, DQuat as Quat, DVec3 as Vec3};
 
 /// Represents an object's orientation in 3D space, using a quaternion.
 /// This struct wraps a [Quat] and provides common rotation operations.
@@ -86,6 +86,77 @@ impl Rotation {
     pub fn normalize(&self) -> Self {
         Self::new(self.0.normalize())
     }
+
+    /// Creates a [Rotation] of `angle` radians around `axis`.
+    ///
+    /// # Arguments
+    /// * `axis` - The axis to rotate around, does not need to be normalized ([Vec3])
+    /// * `angle` - The angle in radians
+    ///
+    /// # Returns
+    /// A new [Rotation] representing the specified rotation
+    #[inline]
+    #[must_use]
+    pub fn from_axis_angle(axis: Vec3, angle: f64) -> Self {
+        Self(Quat::from_axis_angle(axis.normalize(), angle))
+    }
+
+    /// Decomposes the rotation into a unit axis and an angle.
+    ///
+    /// # Returns
+    /// The axis of rotation and the angle in radians, within `[0, 2π]`
+    #[inline]
+    #[must_use]
+    pub fn to_axis_angle(&self) -> (Vec3, f64) {
+        self.0.to_axis_angle()
+    }
+
+    /// Creates a [Rotation] from a rotation vector, whose direction is the axis and length is
+    /// the angle in radians.
+    ///
+    /// # Arguments
+    /// * `v` - The rotation vector ([Vec3])
+    ///
+    /// # Returns
+    /// A new [Rotation] representing the specified rotation
+    #[inline]
+    #[must_use]
+    pub fn from_rotation_vector(v: Vec3) -> Self {
+        Self(Quat::from_scaled_axis(v))
+    }
+
+    /// Converts the rotation into a rotation vector.
+    ///
+    /// # Returns
+    /// A [Vec3] along the axis of rotation with the angle in radians as its length
+    #[inline]
+    #[must_use]
+    pub fn to_rotation_vector(&self) -> Vec3 {
+        self.0.to_scaled_axis()
+    }
+
+    /// Creates a [Rotation] from a rotation matrix.
+    ///
+    /// # Arguments
+    /// * `mat` - An orthonormal matrix with a determinant of one ([Mat3])
+    ///
+    /// # Returns
+    /// A new [Rotation] representing the same rotation as the matrix
+    #[inline]
+    #[must_use]
+    pub fn from_mat3(mat: &Mat3) -> Self {
+        Self(Quat::from_mat3(mat))
+    }
+
+    /// Converts the rotation into a rotation matrix.
+    ///
+    /// # Returns
+    /// A [Mat3] representing the same rotation
+    #[inline]
+    #[must_use]
+    pub fn to_mat3(&self) -> Mat3 {
+        Mat3::from_quat(self.0)
+    }
 }
 
 /// Implements conversion from [Quat] to [Rotation].
@@ -98,7 +169,6 @@ impl From<Quat> for Rotation {
     /// # Returns
     /// A new [Rotation] wrapping the input quaternion
     #[inline]
-    #[must_use]
     fn from(value: Quat) -> Self {
         Self::new(value)
     }
@@ -114,12 +184,27 @@ impl From<Rotation> for Quat {
     /// # Returns
     /// The underlying quaternion from the [Rotation]
     #[inline]
-    #[must_use]
     fn from(value: Rotation) -> Self {
         value.0
     }
 }
 
+/// Implements conversion from [Mat3] to [Rotation].
+impl From<Mat3> for Rotation {
+    #[inline]
+    fn from(value: Mat3) -> Self {
+        Self::from_mat3(&value)
+    }
+}
+
+/// Implements conversion from [Rotation] to [Mat3].
+impl From<Rotation> for Mat3 {
+    #[inline]
+    fn from(value: Rotation) -> Self {
+        value.to_mat3()
+    }
+}
+
 /// Implements conversion from [Transform] to [Rotation].
 impl From<Transform> for Rotation {
     /// Extracts the rotation component from a [Transform].
@@ -130,7 +215,6 @@ impl From<Transform> for Rotation {
     /// # Returns
     /// The [Rotation] component of the input [Transform]
     #[inline]
-    #[must_use]
     fn from(value: Transform) -> Self {
         value.rotation
     }
@@ -148,7 +232,6 @@ impl Sum for Rotation {
     /// # Returns
     /// The combined [Rotation] representing the sum of all rotations in the iterator
     #[inline]
-    #[must_use]
     fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
         iter.fold(Self::ZERO, |a, b| a + b)
     }
@@ -310,6 +393,28 @@ mod tests {
 
             Ok(())
         }
+
+        #[apply(single_quat)]
+        fn axis_angle(#[case] q: Quat) {
+            let r = Rotation::new(q);
+            let (axis, angle) = r.to_axis_angle();
+
+            assert_ulps_eq!(Rotation::from_axis_angle(axis * 3., angle).0, q);
+            assert_ulps_eq!(
+                Rotation::from_rotation_vector(r.to_rotation_vector()).0,
+                q,
+                epsilon = 1e-15
+            );
+        }
+
+        #[apply(single_quat)]
+        fn mat3(#[case] q: Quat) {
+            let r = Rotation::new(q);
+            let m: Mat3 = r.into();
+
+            assert_ulps_eq!(m, Mat3::from_quat(q));
+            assert!(Rotation::from(m).0.dot(q).abs() > 1. - 1e-14);
+        }
     }
 
     #[cfg(test)]
@@ -7,9 +7,8 @@ use {
 };
 
 use super::Transform;
+use crate::quantity::vector_ops;
 use glam::DVec3 as Vec3;
-use overload::overload;
-use std::{iter::Sum, ops};
 
 /// Represents a 3D translation vector.
 ///
@@ -102,52 +101,15 @@ impl Translation {
     }
 }
 
-/// Implements conversion from [Vec3] to [Translation].
-impl From<Vec3> for Translation {
-    #[inline]
-    #[must_use]
-    fn from(value: Vec3) -> Self {
-        Self::from_vec3(value)
-    }
-}
-
-/// Implements conversion from [Translation] to [Vec3].
-impl From<Translation> for Vec3 {
-    #[inline]
-    #[must_use]
-    fn from(value: Translation) -> Self {
-        value.0
-    }
-}
-
 /// Implements conversion from [Transform] to [Translation].
 impl From<Transform> for Translation {
     #[inline]
-    #[must_use]
     fn from(value: Transform) -> Self {
         value.translation
     }
 }
 
-impl Sum for Translation {
-    #[inline]
-    #[must_use]
-    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
-        iter.fold(Self::ZERO, |a, b| a + b)
-    }
-}
-
-overload!((a: ?Translation) + (b: ?Translation) -> Translation{ Translation( a.0 + b.0 ) });
-overload!((a: ?Translation) - (b: ?Translation) -> Translation{ Translation( a.0 - b.0 ) });
-overload!((a: &mut Translation) += (b: ?Translation) { a.0 += b.0 });
-overload!((a: &mut Translation) -= (b: ?Translation) { a.0 -= b.0 });
-
-overload!((a: ?Translation) * (b: f64) -> Translation{ Translation( a.0 * b ) });
-overload!((a: ?Translation) / (b: f64) -> Translation{ Translation( a.0 / b ) });
-overload!((a: &mut Translation) *= (b: f64) { a.0 *= b });
-overload!((a: &mut Translation) /= (b: f64) { a.0 /= b });
-
-overload!(-(a: ?Translation) -> Translation{ Translation( -a.0 ) });
+vector_ops!(Translation);
 
 #[cfg(test)]
 mod tests {
@@ -1,88 +1,19 @@
-#[cfg(feature = "serde")]
-use serde::{Deserialize, Serialize};
 #[cfg(feature = "approx")]
-use {
-    approx::{AbsDiffEq, RelativeEq, UlpsEq},
-    approx_derive::Approx,
-};
+use approx::{AbsDiffEq, RelativeEq, UlpsEq};
 
 use super::{LinVel, Velocity};
-use crate::transform::Rotation;
+use crate::overload::overload;
+use crate::{quantity::vector_quantity, transform::Rotation};
+use core::time::Duration;
 use glam::{DQuat as Quat, DVec3 as Vec3};
-use overload::overload;
-use std::{iter::Sum, ops, time::Duration};
-
-/// Angular velocity in 3D space.
-///
-/// This struct wraps a [Vec3] to provide a strongly typed representation of angular velocity,
-/// making operations and transformations explicit.
-#[cfg_attr(feature = "approx", derive(Approx))]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug, Default, Clone, Copy, PartialEq)]
-pub struct AngVel(pub Vec3);
 
-impl AngVel {
-    /// A zero angular velocity vector.
-    pub const ZERO: Self = Self::splat(0.);
-
-    /// Angular velocity of magnitude one in all directions.
-    pub const ONE: Self = Self::splat(1.);
-
-    /// Unit angular velocity in the positive X direction.
-    pub const X: Self = Self::with_x(1.);
-    /// Unit angular velocity in the positive Y direction.
-    pub const Y: Self = Self::with_y(1.);
-    /// Unit angular velocity in the positive Z direction.
-    pub const Z: Self = Self::with_z(1.);
-
-    /// Unit angular velocity in the negative X direction.
-    pub const NEG_X: Self = Self::with_x(-1.);
-    /// Unit angular velocity in the negative Y direction.
-    pub const NEG_Y: Self = Self::with_y(-1.);
-    /// Unit angular velocity in the negative Z direction.
-    pub const NEG_Z: Self = Self::with_z(-1.);
-
-    /// Creates a new [AngVel] with the specified `x`, `y`, and `z` components.
-    #[inline]
-    #[must_use]
-    pub const fn new(x: f64, y: f64, z: f64) -> Self {
-        Self(Vec3::new(x, y, z))
-    }
-
-    /// Creates an [AngVel] from an existing [Vec3].
-    #[inline]
-    #[must_use]
-    pub const fn from_vec3(v: Vec3) -> Self {
-        Self(v)
-    }
-
-    /// Creates an [AngVel] where all components are set to `v`.
-    #[inline]
-    #[must_use]
-    pub const fn splat(v: f64) -> Self {
-        Self::new(v, v, v)
-    }
-
-    /// Creates an [AngVel] with only the X component set.
-    #[inline]
-    #[must_use]
-    pub const fn with_x(x: f64) -> Self {
-        Self::new(x, 0., 0.)
-    }
-
-    /// Creates an [AngVel] with only the Y component set.
-    #[inline]
-    #[must_use]
-    pub const fn with_y(y: f64) -> Self {
-        Self::new(0., y, 0.)
-    }
-
-    /// Creates an [AngVel] with only the Z component set.
-    #[inline]
-    #[must_use]
-    pub const fn with_z(z: f64) -> Self {
-        Self::new(0., 0., z)
-    }
+vector_quantity! {
+    /// Angular velocity in 3D space.
+    ///
+    /// This struct wraps a [Vec3] to provide a strongly typed representation of angular velocity,
+    /// making operations and transformations explicit.
+    pub struct AngVel;
+    "angular velocity"
 }
 
 impl AngVel {
@@ -120,52 +51,16 @@ impl AngVel {
     }
 }
 
-impl From<AngVel> for Vec3 {
-    #[inline]
-    #[must_use]
-    fn from(value: AngVel) -> Self {
-        value.0
-    }
-}
-impl From<Vec3> for AngVel {
-    #[inline]
-    #[must_use]
-    fn from(value: Vec3) -> Self {
-        Self::from_vec3(value)
-    }
-}
-
 impl From<Velocity> for AngVel {
     #[inline]
-    #[must_use]
     fn from(value: Velocity) -> Self {
         value.angular
     }
 }
 
-impl Sum for AngVel {
-    #[inline]
-    #[must_use]
-    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
-        iter.fold(Self::ZERO, |a, b| a + b)
-    }
-}
-
-overload!((a: ?AngVel) + (b: ?AngVel) -> AngVel{ AngVel( a.0 + b.0 ) });
-overload!((a: ?AngVel) - (b: ?AngVel) -> AngVel{ AngVel( a.0 - b.0 ) });
-overload!((a: &mut AngVel) += (b: ?AngVel) { a.0 += b.0 });
-overload!((a: &mut AngVel) -= (b: ?AngVel) { a.0 -= b.0 });
-
 overload!((a: ?AngVel) * (b: Duration) -> Rotation{ a.mul_dur(&b) });
 overload!((a: ?AngVel) * (b: &Duration) -> Rotation{ a.mul_dur(b) });
 
-overload!((a: ?AngVel) * (b: f64) -> AngVel{ AngVel( a.0 * b ) });
-overload!((a: ?AngVel) / (b: f64) -> AngVel{ AngVel( a.0 / b ) });
-overload!((a: &mut AngVel) *= (b: f64) { a.0 *= b });
-overload!((a: &mut AngVel) /= (b: f64) { a.0 /= b });
-
-overload!(-(a: ?AngVel) -> AngVel{ AngVel( -a.0 ) });
-
 #[cfg(test)]
 mod constructors {
     use approx::assert_ulps_eq;
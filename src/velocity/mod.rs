@@ -6,13 +6,13 @@ use {
     approx_derive::Approx,
 };
 
+use crate::overload::overload;
 pub use angular_velocity::AngVel;
+use core::{iter::Sum, time::Duration};
 use glam::DVec3 as Vec3;
 pub use linear_velocity::LinVel;
-use overload::overload;
-use std::{iter::Sum, ops, time::Duration};
 
-use crate::transform::Transform;
+use crate::{acceleration::Acceleration, transform::Transform};
 
 mod angular_velocity;
 mod linear_velocity;
@@ -101,12 +101,27 @@ impl Velocity {
     pub fn mul_dur(&self, rhs: &Duration) -> Transform {
         self.mul_secs(rhs.as_secs_f64())
     }
+
+    /// Divides the velocity by a time duration in seconds, returning the average [Acceleration].
+    #[inline]
+    #[must_use]
+    pub fn div_secs(&self, rhs: f64) -> Acceleration {
+        Acceleration::new(self.linear.div_secs(rhs), self.angular.div_secs(rhs))
+    }
+
+    /// Divides the velocity by a [Duration], returning the average [Acceleration].
+    ///
+    /// Internally, this calls [Velocity::div_secs] using [Duration::as_secs_f64].
+    #[inline]
+    #[must_use]
+    pub fn div_dur(&self, rhs: &Duration) -> Acceleration {
+        self.div_secs(rhs.as_secs_f64())
+    }
 }
 
 /// Conversion implementations to create `Velocity` from individual components.
 impl From<LinVel> for Velocity {
     #[inline]
-    #[must_use]
     fn from(value: LinVel) -> Self {
         Self::from_linear(value)
     }
@@ -114,7 +129,6 @@ impl From<LinVel> for Velocity {
 
 impl From<AngVel> for Velocity {
     #[inline]
-    #[must_use]
     fn from(value: AngVel) -> Self {
         Self::from_angular(value)
     }
@@ -123,7 +137,6 @@ impl From<AngVel> for Velocity {
 /// Implements summation over an iterator of `Velocity` values.
 impl Sum for Velocity {
     #[inline]
-    #[must_use]
     fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
         iter.fold(Self::ZERO, |a, b| a + b)
     }
@@ -140,6 +153,8 @@ overload!((a: &mut Velocity) *= (b: f64) { a.linear *= b; a.angular *= b; });
 overload!((a: &mut Velocity) /= (b: f64) { a.linear /= b; a.angular /= b; });
 
 overload!((a: ?Velocity) * (b: ?Duration) -> Transform{ Transform::new(a.linear * b, a.angular * b) });
+overload!((a: ?Velocity) / (b: Duration) -> Acceleration{ a.div_dur(&b) });
+overload!((a: ?Velocity) / (b: &Duration) -> Acceleration{ a.div_dur(b) });
 
 overload!(-(a: ?Velocity) -> Velocity{Velocity{ linear: -a.linear, angular: -a.angular }});
 